@@ -5,7 +5,7 @@ fn on_found(offset: usize) -> bool {
 }
 
 /// Runs all the batched scans in the given slice.
-fn scan_batch(batch: &Vec<aobscan::Pattern>, data: &[u8]) {
+fn scan_batch(batch: &[aobscan::Pattern], data: &[u8]) {
     for pattern in batch.iter() {
         println!("Scanning for pattern: {}", pattern);
         pattern.scan(data, on_found);
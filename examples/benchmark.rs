@@ -1,4 +1,5 @@
-use rand::RngCore;
+use rand::{RngCore, SeedableRng};
+use rand::rngs::StdRng;
 
 /// Size of the random data to generate.
 const BLOCK_SIZE: usize = 1024 * 1024 * 512;
@@ -6,6 +7,10 @@ const BLOCK_SIZE: usize = 1024 * 1024 * 512;
 /// Number of scans to perform. (on different random data)
 const BLOCKS: usize = 5;
 
+/// Seed of the random data, so that the results are comparable across runs.
+/// Each block is generated with the seed incremented by its index.
+const SEED: u64 = 0x0B5CA4;
+
 /// This example benchmarks the scan performance in a single-threaded and
 /// multi-threaded context.
 ///
@@ -33,8 +38,8 @@ fn main() {
 fn run_single_threaded() -> std::time::Duration {
     let mut total_time = std::time::Duration::new(0, 0);
 
-    for _ in 0..5 {
-        let data = random_bytes(BLOCK_SIZE);
+    for block in 0..BLOCKS {
+        let data = random_bytes(BLOCK_SIZE, SEED + block as u64);
         let start = std::time::Instant::now();
 
         aobscan::PatternBuilder::from_ida_style("48 8B")
@@ -56,8 +61,8 @@ fn run_single_threaded() -> std::time::Duration {
 fn run_multi_threaded() -> std::time::Duration {
     let mut total_time = std::time::Duration::new(0, 0);
 
-    for _ in 0..5 {
-        let data = random_bytes(BLOCK_SIZE);
+    for block in 0..BLOCKS {
+        let data = random_bytes(BLOCK_SIZE, SEED + block as u64);
         let start = std::time::Instant::now();
 
         aobscan::PatternBuilder::from_ida_style("48 8B")
@@ -76,8 +81,8 @@ fn run_multi_threaded() -> std::time::Duration {
 }
 
 //noinspection ALL
-fn random_bytes(len: usize) -> Vec<u8> {
-    let mut rng = rand::thread_rng();
+fn random_bytes(len: usize, seed: u64) -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(seed);
     let mut bytes = vec![0u8; len];
     rng.fill_bytes(&mut bytes);
     bytes
@@ -86,6 +91,5 @@ fn random_bytes(len: usize) -> Vec<u8> {
 /// Gets the GB/s from a time duration and byte count.
 fn get_gbps(time: std::time::Duration, bytes: usize) -> f64 {
     let bytes_per_second = bytes as f64 / time.as_secs_f64();
-    let gbps = bytes_per_second / 1024.0 / 1024.0 / 1024.0;
-    gbps
+    bytes_per_second / 1024.0 / 1024.0 / 1024.0
 }
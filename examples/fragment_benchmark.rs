@@ -0,0 +1,44 @@
+/// Size of the data to generate.
+const BLOCK_SIZE: usize = 1024 * 1024 * 512;
+
+/// This example benchmarks the scan performance of a wildcard-heavy pattern whose
+/// fixed bytes form a single run. (`? ? ? 90 90 90 90 ? ?`)
+///
+/// The example generates a data buffer made of `90 90 90 CC` repeated, so that every
+/// fixed byte of the pattern is found at most positions, but the whole run of fixed
+/// bytes is never found.
+///
+/// Threading: Single-threaded
+/// Hits: None
+fn main() {
+    println!("Block: {:#02x} bytes", BLOCK_SIZE);
+
+    let data = [0x90, 0x90, 0x90, 0xCC].repeat(BLOCK_SIZE / 4);
+    let pattern = "? ? ? 90 90 90 90 ? ?";
+
+    let mut time = run(&data, pattern, false);
+    println!("First byte anchor: {:?} @ {:.2} GB/s", time, get_gbps(time, BLOCK_SIZE));
+
+    time = run(&data, pattern, true);
+    println!("Longest fragment: {:?} @ {:.2} GB/s", time, get_gbps(time, BLOCK_SIZE));
+}
+
+fn run(data: &[u8], pattern: &str, dual_anchor: bool) -> std::time::Duration {
+    let pattern = aobscan::PatternBuilder::from_ida_style(pattern)
+        .unwrap()
+        .with_dual_anchor(dual_anchor)
+        .build();
+
+    let start = std::time::Instant::now();
+    pattern.scan(data, move |_| {
+        true // Return true to continue scanning for other matches
+    });
+
+    std::time::Instant::now() - start
+}
+
+/// Gets the GB/s from a time duration and byte count.
+fn get_gbps(time: std::time::Duration, bytes: usize) -> f64 {
+    let bytes_per_second = bytes as f64 / time.as_secs_f64();
+    bytes_per_second / 1024.0 / 1024.0 / 1024.0
+}
@@ -0,0 +1,54 @@
+/// Size of the data to generate.
+const BLOCK_SIZE: usize = 1024 * 1024 * 512;
+
+/// Distance between the candidates of the pattern in the data.
+const CANDIDATE_STRIDE: usize = 64;
+
+/// Number of wildcards between the fixed bytes of the pattern.
+const GAP: usize = 200;
+
+/// This example benchmarks the scan performance of a pattern with a long gap of
+/// wildcards between its fixed bytes. (`48 8B`, 200 wildcards, `90`)
+///
+/// The example generates a data buffer filled with `90`, in which the first two
+/// bytes of the pattern are planted at a fixed stride, so that the signature must be
+/// compared at every planted position when the first byte is the only anchor.
+///
+/// Threading: Single-threaded
+/// Hits: All
+fn main() {
+    println!("Block: {:#02x} bytes", BLOCK_SIZE);
+
+    let mut data = vec![0x90u8; BLOCK_SIZE];
+    for offset in (0..BLOCK_SIZE - 1).step_by(CANDIDATE_STRIDE) {
+        data[offset..offset + 2].copy_from_slice(&[0x48, 0x8B]);
+    }
+
+    let pattern = format!("48 8B {}90", "? ".repeat(GAP));
+
+    let mut time = run(&data, &pattern, false);
+    println!("Single anchor: {:?} @ {:.2} GB/s", time, get_gbps(time, BLOCK_SIZE));
+
+    time = run(&data, &pattern, true);
+    println!("Dual anchor: {:?} @ {:.2} GB/s", time, get_gbps(time, BLOCK_SIZE));
+}
+
+fn run(data: &[u8], pattern: &str, dual_anchor: bool) -> std::time::Duration {
+    let pattern = aobscan::PatternBuilder::from_ida_style(pattern)
+        .unwrap()
+        .with_dual_anchor(dual_anchor)
+        .build();
+
+    let start = std::time::Instant::now();
+    pattern.scan(data, move |_| {
+        true // Return true to continue scanning for other matches
+    });
+
+    std::time::Instant::now() - start
+}
+
+/// Gets the GB/s from a time duration and byte count.
+fn get_gbps(time: std::time::Duration, bytes: usize) -> f64 {
+    let bytes_per_second = bytes as f64 / time.as_secs_f64();
+    bytes_per_second / 1024.0 / 1024.0 / 1024.0
+}
@@ -0,0 +1,61 @@
+use rand::{RngCore, SeedableRng};
+use rand::rngs::StdRng;
+
+/// Size of the random data to generate.
+const BLOCK_SIZE: usize = 1024 * 1024 * 512;
+
+/// Seed of the random data, so that the results are comparable across runs.
+const SEED: u64 = 0x0B5CA4;
+
+/// Wildcard-heavy pattern, in which the first fixed byte is very common.
+const PATTERN: &str = "00 ? ? ? ? ? ? ? ? ? ? ? 5A ? ? ? ? ? ? ? ? ? ? ? ? ? ? ? C7";
+
+/// This example benchmarks the scan performance of a wildcard-heavy pattern,
+/// using a single anchor byte and two anchor bytes.
+///
+/// The example generates a random data buffer, in which a quarter of the bytes
+/// are zeroed to simulate the padding of real executables, and then scans it for
+/// a pattern that starts with a zero byte.
+///
+/// Threading: Single-threaded
+/// Hits: All
+fn main() {
+    println!("Block: {:#02x} bytes", BLOCK_SIZE);
+
+    let data = random_bytes(BLOCK_SIZE, SEED);
+
+    let mut time = run(&data, false);
+    println!("Single anchor: {:?} @ {:.2} GB/s", time, get_gbps(time, BLOCK_SIZE));
+
+    time = run(&data, true);
+    println!("Dual anchor: {:?} @ {:.2} GB/s", time, get_gbps(time, BLOCK_SIZE));
+}
+
+fn run(data: &[u8], dual_anchor: bool) -> std::time::Duration {
+    let pattern = aobscan::PatternBuilder::from_ida_style(PATTERN)
+        .unwrap()
+        .with_dual_anchor(dual_anchor)
+        .build();
+
+    let start = std::time::Instant::now();
+    pattern.scan(data, move |_| {
+        true // Return true to continue scanning for other matches
+    });
+
+    std::time::Instant::now() - start
+}
+
+//noinspection ALL
+fn random_bytes(len: usize, seed: u64) -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut bytes = vec![0u8; len];
+    rng.fill_bytes(&mut bytes);
+    bytes.iter_mut().step_by(4).for_each(|byte| *byte = 0);
+    bytes
+}
+
+/// Gets the GB/s from a time duration and byte count.
+fn get_gbps(time: std::time::Duration, bytes: usize) -> f64 {
+    let bytes_per_second = bytes as f64 / time.as_secs_f64();
+    bytes_per_second / 1024.0 / 1024.0 / 1024.0
+}
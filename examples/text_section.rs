@@ -19,9 +19,10 @@ fn main() {
         .build()
         .scan_object(&data, section_name, move |result| {
             println!(
-                "{:#02x} [{} {}+{:#02x}]",
+                "{:#02x} [{} {},{}+{:#02x}]",
                 result.raw_offset,
                 result.archive_id.unwrap_or("".to_string()),
+                result.segment_name.unwrap_or("".to_string()),
                 section_name,
                 result.section_offset
             );
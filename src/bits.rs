@@ -0,0 +1,68 @@
+/// Performs a scan for a bit pattern in the given slice, at any bit offset.<br><br>
+///
+/// This is useful for bitstream formats, whose fields are not aligned to bytes.<br>
+/// The bits of each byte are read from the most significant to the least significant,
+/// so the bit offset `n` refers to the bit `7 - n % 8` of the byte `n / 8`.<br>
+/// The scan is always single-threaded.<br><br>
+///
+/// # Arguments
+/// * `data` - The data slice to scan.
+/// * `bit_pattern` - The bits to search for.
+/// * `bit_mask` - The mask of the bit pattern, containing `false` for the wildcard bits.
+/// * `callback` - The callback to execute when a match is found.
+///    - The callback receives the bit offset of the match as an argument.
+///    - It should return `true` to continue scanning, or `false` to stop.
+///
+/// # Returns
+/// True if at least one match was found, otherwise false.<br>
+/// If the bit pattern is empty or its length does not match the length of the
+/// mask, no scan is performed and false is returned.
+///
+/// # Example
+/// ```
+/// // 0b1010_1100 0b1100_0000
+/// let data = [0xAC, 0xC0];
+/// let bits = [true, true, false, false, true, true];
+///
+/// let mut offsets = vec![];
+/// aobscan::scan_bits(&data, &bits, &[true; 6], |offset| {
+///     offsets.push(offset);
+///     true
+/// });
+/// assert_eq!(offsets, vec![4]);
+/// ```
+pub fn scan_bits(
+    data: &[u8],
+    bit_pattern: &[bool],
+    bit_mask: &[bool],
+    mut callback: impl FnMut(usize) -> bool,
+) -> bool {
+    if bit_pattern.is_empty() || bit_pattern.len() != bit_mask.len() {
+        return false;
+    }
+
+    let total_bits = data.len().saturating_mul(8);
+    let last = match total_bits.checked_sub(bit_pattern.len()) {
+        Some(last) => last,
+        None => return false,
+    };
+
+    let bit_at = |offset: usize| (data[offset / 8] >> (7 - offset % 8)) & 1 == 1;
+
+    let mut found = false;
+    for offset in 0..=last {
+        let matches = bit_pattern.iter()
+            .zip(bit_mask)
+            .enumerate()
+            .all(|(i, (&bit, &fixed))| !fixed || bit_at(offset + i) == bit);
+
+        if matches {
+            found = true;
+            if !callback(offset) {
+                break;
+            }
+        }
+    }
+
+    found
+}
@@ -1,17 +1,37 @@
+use std::ops::Range;
+
 use super::Pattern;
 
 /// An error in the pattern builder.<br>
 /// This encapsulates all possible errors that can occur when building a pattern.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum BuilderError {
-    /// Thrown when the signature's byte parsing fails.
+    /// Thrown when the signature's byte parsing fails.<br>
+    /// This is no longer returned by the parsers, which report the invalid token and its
+    /// position with [`ParseAt`](#variant.ParseAt) instead.
+    #[deprecated(note = "the parsers return `BuilderError::ParseAt` instead")]
     ParseError(std::num::ParseIntError),
     /// Thrown when the size of the signature differs from the size of the mask.
     SizeMismatch,
     /// Thrown when the signature is empty or invalid.
     InvalidSignature(String),
+    /// Thrown when a token of the pattern string is not a valid byte.<br>
+    /// This replaces [`ParseError`](#variant.ParseError) for all the parsers.
+    ParseAt {
+        /// The byte index of the token in the pattern string.
+        index: usize,
+        /// The invalid token.
+        token: String,
+    },
     /// Thrown when the selected worker threads count is invalid.
     InvalidThreadCount,
+    /// Thrown when a line of a signature database is invalid.
+    AtLine {
+        /// The number of the line, starting from `1`.
+        line: usize,
+        /// The error of the line.
+        error: Box<BuilderError>,
+    },
 }
 
 impl std::fmt::Display for BuilderError {
@@ -24,10 +44,13 @@ impl std::fmt::Display for BuilderError {
     /// Whether the formatting was successful or not.
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            #[allow(deprecated)]
             Self::ParseError(err) => write!(f, "{}", err),
             Self::SizeMismatch => write!(f, "the size of signature and mask do not match"),
             Self::InvalidSignature(message) => write!(f, "{}", message),
+            Self::ParseAt { index, token } => write!(f, "invalid token '{}' at index {}", token, index),
             Self::InvalidThreadCount => write!(f, "the thread count must be greater than zero and less than or equal to the number of logical cores"),
+            Self::AtLine { line, error } => write!(f, "line {}: {}", line, error),
         }
     }
 }
@@ -35,18 +58,296 @@ impl std::fmt::Display for BuilderError {
 impl std::error::Error for BuilderError {}
 
 impl From<std::num::ParseIntError> for BuilderError {
-    /// Converts a `ParseIntError` into a `BuilderError`.<br><br>
+    /// Converts a `ParseIntError` into a `BuilderError`.<br>
+    /// The parsers of this crate don't use this conversion, so the error is not
+    /// associated with a token. (see [`BuilderError::ParseAt`](enum.BuilderError.html#variant.ParseAt))<br><br>
     ///
     /// # Arguments
     /// * `err` - The error to convert.
     ///
     /// # Returns
     /// The converted error.
+    #[allow(deprecated)]
     fn from(err: std::num::ParseIntError) -> Self {
         Self::ParseError(err)
     }
 }
 
+/// The bytes of a signature, including the placeholders of the wildcard bytes.<br><br>
+///
+/// Together with [`Mask`](struct.Mask.html), this documents the meaning of the
+/// arguments of [`PatternBuilder::from_signature_mask`](struct.PatternBuilder.html#method.from_signature_mask),
+/// so that they can't be swapped by mistake.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Signature(pub Vec<u8>);
+
+impl From<&[u8]> for Signature {
+    fn from(bytes: &[u8]) -> Self {
+        Self(bytes.to_vec())
+    }
+}
+
+impl From<Vec<u8>> for Signature {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+/// The mask of a signature, in which the wildcard bytes are represented by `false`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Mask(pub Vec<bool>);
+
+impl From<&[bool]> for Mask {
+    fn from(mask: &[bool]) -> Self {
+        Self(mask.to_vec())
+    }
+}
+
+impl From<Vec<bool>> for Mask {
+    fn from(mask: Vec<bool>) -> Self {
+        Self(mask)
+    }
+}
+
+/// The textual styles in which a pattern can be represented.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PatternStyle {
+    /// IDA-style pattern. (e.g. `48 8B ? ?`)
+    Ida,
+    /// Non-spaced string of hexadecimal bytes. (e.g. `488b????`)
+    Hex,
+    /// Code-style signature and mask. (e.g. `\x48\x8B\x00\x00` and `..??`)
+    Code,
+}
+
+/// The byte order of the integers encoded in a pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Endian {
+    /// Least significant byte first. (e.g. x86, ARM)
+    Little,
+    /// Most significant byte first. (e.g. network byte order, PowerPC)
+    Big,
+}
+
+impl Endian {
+    /// Encodes a 32-bit integer in this byte order.<br><br>
+    ///
+    /// # Arguments
+    /// * `value` - The integer to encode.
+    ///
+    /// # Returns
+    /// The bytes of the integer.
+    pub(crate) fn u32_bytes(self, value: u32) -> [u8; 4] {
+        match self {
+            Self::Little => value.to_le_bytes(),
+            Self::Big => value.to_be_bytes(),
+        }
+    }
+
+    /// Decodes a 32-bit integer in this byte order.<br><br>
+    ///
+    /// # Arguments
+    /// * `bytes` - The bytes of the integer.
+    ///
+    /// # Returns
+    /// The decoded integer.
+    pub(crate) fn read_u32(self, bytes: [u8; 4]) -> u32 {
+        match self {
+            Self::Little => u32::from_le_bytes(bytes),
+            Self::Big => u32::from_be_bytes(bytes),
+        }
+    }
+}
+
+/// Parses a pattern whose style is not known in advance.<br><br>
+///
+/// The input is parsed as an IDA-style pattern first, and then as a hexadecimal
+/// string.<br>
+/// Code-style patterns are never detected, as they require a separate mask.<br><br>
+///
+/// # Arguments
+/// * `input` - The pattern string.
+///
+/// # Returns
+/// The built pattern and the style that was used to parse it.<br><br>
+///
+/// # Errors
+/// If the input is not valid in any style, the error of the style that the input
+/// most resembles is returned. (IDA-style if it contains whitespaces, hexadecimal otherwise)
+pub fn detect_and_parse(input: &str) -> Result<(Pattern, PatternStyle), BuilderError> {
+    match PatternBuilder::from_ida_style(input) {
+        Ok(builder) => Ok((builder.build(), PatternStyle::Ida)),
+        Err(ida_err) => match PatternBuilder::from_hex_string(input) {
+            Ok(builder) => Ok((builder.build(), PatternStyle::Hex)),
+            Err(_) if input.trim().contains(char::is_whitespace) => Err(ida_err),
+            Err(hex_err) => Err(hex_err),
+        },
+    }
+}
+
+/// Builds a pattern that matches two versions of the same data, around the first
+/// byte in which they differ.<br><br>
+///
+/// The pattern contains the bytes of `a` from `around` bytes before the first difference
+/// to `around` bytes after it, and every byte of this window that differs between the
+/// two buffers is a wildcard.<br>
+/// The window is shortened at the start and at the end of the buffers.<br><br>
+///
+/// # Arguments
+/// * `a` - The first version of the data.
+/// * `b` - The second version of the data.
+/// * `around` - The number of bytes of context on each side of the first difference.
+///
+/// # Returns
+/// The pattern, or `None` if the buffers do not differ in their common length.
+///
+/// # Example
+/// ```
+/// let pattern = aobscan::pattern_from_diff(b"\x48\x8B\x05\x10\x90", b"\x48\x8B\x05\x20\x90", 2).unwrap();
+/// assert_eq!(pattern.to_ida_style(), "8B 05 ? 90");
+/// ```
+pub fn pattern_from_diff(a: &[u8], b: &[u8], around: usize) -> Option<Pattern> {
+    let first = a.iter().zip(b).position(|(a, b)| a != b)?;
+
+    let start = first.saturating_sub(around);
+    let end = first.saturating_add(around).saturating_add(1).min(a.len()).min(b.len());
+    let bytes = a[start..end].iter()
+        .zip(&b[start..end])
+        .map(|(&a, &b)| (a == b).then_some(a));
+
+    // The window is never empty, as it contains the first difference.
+    PatternBuilder::from_iter_opt(bytes).ok().map(PatternBuilder::build)
+}
+
+/// Parses a database of named IDA-style signatures.<br><br>
+///
+/// Each line of the database contains a name and a signature separated by `=`.<br>
+/// Blank lines and lines starting with `#` are ignored, and the whitespaces around
+/// the names and the signatures are trimmed.<br><br>
+///
+/// # Arguments
+/// * `text` - The contents of the database.
+///
+/// # Returns
+/// The names and the built patterns, in the order of the lines.<br><br>
+///
+/// # Errors
+/// * `BuilderError::AtLine` - The number of the first invalid line, with the error of its
+///   signature, or `BuilderError::InvalidSignature` if it is not made of a name and a signature.
+///
+/// # Example
+/// ```
+/// let db = "# Functions\nGetPlayer = 48 8B 05 ? ? ? ?\n";
+/// let patterns = aobscan::load_sig_db(db).unwrap();
+///
+/// assert_eq!(patterns[0].0, "GetPlayer");
+/// assert_eq!(patterns[0].1.to_ida_style(), "48 8B 05");
+/// ```
+pub fn load_sig_db(text: &str) -> Result<Vec<(String, Pattern)>, BuilderError> {
+    let mut patterns = vec![];
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let at_line = |error| BuilderError::AtLine { line: index + 1, error: Box::new(error) };
+        let (name, signature) = line.split_once('=')
+            .map(|(name, signature)| (name.trim(), signature.trim()))
+            .filter(|(name, _)| !name.is_empty())
+            .ok_or_else(|| at_line(BuilderError::InvalidSignature(
+                "expected a line in the form 'name = signature'".to_string(),
+            )))?;
+
+        let pattern = PatternBuilder::from_ida_style(signature).map_err(at_line)?.build();
+        patterns.push((name.to_string(), pattern));
+    }
+    Ok(patterns)
+}
+
+/// Converts a pattern string from a style to another.<br><br>
+///
+/// As code-style patterns are made of two parts, they are represented as a single
+/// string containing the escaped signature and the mask, separated by a whitespace.
+/// (e.g. `\x48\x8B\x00\x00 ..??`)<br>
+/// Trailing wildcards are not preserved by the conversion.<br><br>
+///
+/// # Arguments
+/// * `input` - The pattern string.
+/// * `from` - The style of the input string.
+/// * `to` - The style of the output string.
+///
+/// # Returns
+/// The pattern string in the output style.<br><br>
+///
+/// # Errors
+/// Any error of the builder function of the input style, and:
+/// * `BuilderError::InvalidSignature` - The code-style input is not made of a signature and a mask.
+/// * `BuilderError::ParseAt` - The code-style signature contains an invalid escaped byte.
+///
+/// # Example
+/// ```
+/// use aobscan::PatternStyle;
+///
+/// let hex = aobscan::convert("48 8B ? ? 90", PatternStyle::Ida, PatternStyle::Hex).unwrap();
+/// assert_eq!(hex, "488b????90");
+/// ```
+pub fn convert(input: &str, from: PatternStyle, to: PatternStyle) -> Result<String, BuilderError> {
+    let pattern = match from {
+        PatternStyle::Ida => PatternBuilder::from_ida_style(input)?,
+        PatternStyle::Hex => PatternBuilder::from_hex_string(input)?,
+        PatternStyle::Code => parse_code_string(input)?,
+    }.build();
+
+    Ok(match to {
+        PatternStyle::Ida => pattern.to_ida_style(),
+        PatternStyle::Hex => pattern.to_hex_string(),
+        PatternStyle::Code => {
+            let (signature, mask) = pattern.to_code_style();
+            format!("{} {}", signature, mask)
+        }
+    })
+}
+
+/// Internal function that parses a code-style pattern represented as a single string.
+/// (see [`convert`](fn.convert.html))<br><br>
+///
+/// # Arguments
+/// * `input` - The escaped signature and the mask, separated by a whitespace.
+///
+/// # Returns
+/// The builder of the pattern, or an error if the input is invalid.
+fn parse_code_string(input: &str) -> Result<PatternBuilder, BuilderError> {
+    let (escaped, mask) = input.trim()
+        .split_once(char::is_whitespace)
+        .ok_or_else(|| BuilderError::InvalidSignature(
+            "the code-style pattern must contain a signature and a mask".to_string()
+        ))?;
+
+    // Index of the signature in the input string, used in the errors.
+    let start = input.len() - input.trim_start().len();
+
+    // Each byte of the signature is escaped as `\xHH`.
+    let signature = escaped.as_bytes()
+        .chunks(4)
+        .enumerate()
+        .map(|(index, escape)| {
+            let index = start + index * 4;
+            match escape {
+                [b'\\', b'x', digits @ ..] if digits.len() == 2 => {
+                    parse_byte(&String::from_utf8_lossy(digits), index + 2)
+                }
+                _ => Err(BuilderError::ParseAt {
+                    index,
+                    token: String::from_utf8_lossy(escape).to_string(),
+                }),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    PatternBuilder::from_code_style(&signature, mask.trim())
+}
+
 /// Builder for the Pattern struct.<br>
 /// The builder is used to create a Pattern struct with the desired settings.<br><br>
 ///
@@ -54,13 +355,13 @@ impl From<std::num::ParseIntError> for BuilderError {
 /// - The signature must be a valid hexadecimal string. (case-insensitive)
 /// - It might contain wildcards at the beginning and/or end of the string. (which are ignored during the scan)
 ///   - In the case of one or more wildcard bytes at the beginning of the string, the resulting address will be offset by the number of wildcards.
-/// (resulting in the address of the first non-wildcard byte minus the wildcard bytes at the beginning)
+///     (resulting in the address of the first non-wildcard byte minus the wildcard bytes at the beginning)
 ///   - End wildcards are ignored during the scan, so the match will be found even if the full pattern would not fit in the remaining bytes.
 ///
 /// # Examples
 ///
 /// ## Multi-threaded scan for an IDA-style pattern
-/// ```
+/// ```no_run
 /// let data = std::fs::read("some.bin").unwrap();
 /// let found = aobscan::PatternBuilder::from_ida_style("48 8B 05 ? ? ? ? 48 8B 88 ? ? ? ?")
 ///     .unwrap()
@@ -73,7 +374,7 @@ impl From<std::num::ParseIntError> for BuilderError {
 /// ```
 ///
 /// ## Single-threaded scan for a code-style pattern
-/// ```
+/// ```no_run
 /// let data = std::fs::read("some.bin").unwrap();
 /// let found = aobscan::PatternBuilder::from_code_style(
 ///     b"\x48\x8B\x05\x00\x00\x00\x00\x48\x8B\x88\x00\x00\x00\x00",
@@ -90,11 +391,58 @@ impl From<std::num::ParseIntError> for BuilderError {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct PatternBuilder {
     signature: Vec<u8>,
-    mask: Vec<bool>,
+    mask: Vec<u8>,
     threads: usize,
+    exclusions: Vec<(isize, u8)>,
+    checksums: Vec<(Range<usize>, u32)>,
+    overlapping: bool,
+    min_distance: usize,
+    dual_anchor: bool,
+    auto_threads: bool,
+    optional: Vec<usize>,
 }
 
 impl PatternBuilder {
+    /// Internal function that creates a pattern builder with the default settings.<br><br>
+    ///
+    /// # Arguments
+    /// * `signature` - The signature to scan for.
+    /// * `mask` - The mask in which the wildcard bytes are represented by `false`.
+    ///
+    /// # Returns
+    /// The newly created builder.
+    fn from_parts(signature: Vec<u8>, mask: Vec<bool>) -> Self {
+        let mask = mask.into_iter()
+            .map(|fixed| if fixed { 0xFF } else { 0x00 })
+            .collect();
+
+        Self::from_bit_parts(signature, mask)
+    }
+
+    /// Internal function that creates a pattern builder with the default settings,
+    /// from a signature whose bytes may be only partially fixed.<br><br>
+    ///
+    /// # Arguments
+    /// * `signature` - The signature to scan for.
+    /// * `mask` - The bit masks of the signature bytes. (`0xFF` for fixed bytes, `0x00` for wildcards)
+    ///
+    /// # Returns
+    /// The newly created builder.
+    fn from_bit_parts(signature: Vec<u8>, mask: Vec<u8>) -> Self {
+        Self {
+            signature,
+            mask,
+            threads: 1,
+            exclusions: vec![],
+            checksums: vec![],
+            overlapping: true,
+            min_distance: 0,
+            dual_anchor: true,
+            auto_threads: false,
+            optional: vec![],
+        }
+    }
+
     /// Creates a pattern builder from a code-style signature.<br><br>
     ///
     /// A code-style signature is characterized by a byte array and a mask string.<br>
@@ -119,7 +467,7 @@ impl PatternBuilder {
     /// # Good Practices
     /// * In the mask string, use a `[?]` for each wildcard byte, and `[.]` for each non-wildcard byte.
     /// * In the byte array, use `\x00` for each wildcard byte, and the actual byte
-    /// value for each non-wildcard byte.
+    ///   value for each non-wildcard byte.
     ///
     /// # Errors
     /// * `BuilderError::SizeMismatch` - The size of the signature and mask do not match.
@@ -136,19 +484,140 @@ impl PatternBuilder {
         if signature_bytes.len() != mask_bytes.len() {
             Err(BuilderError::SizeMismatch)
         } else {
-            Ok(Self {
-                signature: signature_bytes,
-                mask: mask_bytes,
-                threads: 1,
-            })
+            Ok(Self::from_parts(signature_bytes, mask_bytes))
+        }
+    }
+
+    /// Creates a pattern builder from a typed signature and mask.<br><br>
+    ///
+    /// This is equivalent to [`from_code_style`](#method.from_code_style), but the types
+    /// of the arguments prevent passing the signature and the mask in the wrong order.<br><br>
+    ///
+    /// # Arguments
+    /// * `signature` - The bytes to search for.
+    /// * `mask` - The mask in which the wildcard bytes are represented by `false`.
+    ///
+    /// # Returns
+    /// The current instance of the builder, or `None` if the parameters are invalid.<br><br>
+    ///
+    /// # Errors
+    /// * `BuilderError::SizeMismatch` - The size of the signature and mask do not match.
+    ///
+    /// # Format
+    /// ```ignore
+    /// signature:  Signature::from(&b"\x48\x8B\x00"[..])
+    /// mask:       Mask(vec![true, true, false])
+    /// ```
+    pub fn from_signature_mask(signature: Signature, mask: Mask) -> Result<Self, BuilderError> {
+        if signature.0.len() != mask.0.len() {
+            Err(BuilderError::SizeMismatch)
+        } else {
+            Ok(Self::from_parts(signature.0, mask.0))
         }
     }
 
+    /// Creates a pattern builder from a signature and a bit mask string.<br><br>
+    ///
+    /// The bit mask string contains a `1` for each significant bit of the signature,
+    /// and a `0` for each ignored bit, starting from the most significant bit of the
+    /// first byte.<br>
+    /// This allows matching bytes that are only partially fixed, such as opcodes that
+    /// encode a register in their lowest bits.<br><br>
+    ///
+    /// # Arguments
+    /// * `signature` - The byte array containing the bytes to search for.
+    /// * `bitmask` - The bit mask string, with 8 characters for each byte of the signature.
+    ///
+    /// # Returns
+    /// The current instance of the builder, or `None` if the parameters are invalid.<br><br>
+    ///
+    /// # Errors
+    /// * `BuilderError::SizeMismatch` - The bit mask does not contain 8 characters for each byte of the signature.
+    /// * `BuilderError::ParseAt` - The bit mask contains characters other than `0` and `1`.
+    ///
+    /// # Format
+    /// ```ignore
+    /// signature:  `b"\x48\x50"`
+    /// bitmask:    `"1111111111111000"` // matches 48 50, 48 51, ..., 48 57
+    /// ```
+    pub fn from_bitmask(signature: &[u8], bitmask: &str) -> Result<Self, BuilderError> {
+        if bitmask.len() != signature.len() * 8 {
+            Err(BuilderError::SizeMismatch)?
+        }
+
+        let mut mask_bytes: Vec<u8> = vec![0; signature.len()];
+        for (index, c) in bitmask.char_indices() {
+            match c {
+                '1' => mask_bytes[index / 8] |= 0x80 >> (index % 8),
+                '0' => {}
+                _ => Err(BuilderError::ParseAt { index, token: c.to_string() })?
+            }
+        }
+
+        Ok(Self::from_bit_parts(signature.to_vec(), mask_bytes))
+    }
+
+    /// Creates a pattern builder that matches a 32-bit integer.<br><br>
+    ///
+    /// This is useful to find the references to an address, or the uses of a constant.<br><br>
+    ///
+    /// # Arguments
+    /// * `value` - The integer to match.
+    /// * `endian` - The byte order of the integer in the data.
+    ///
+    /// # Returns
+    /// The current instance of the builder.
+    ///
+    /// # Format
+    /// ```ignore
+    /// value:      0x00401000 (Endian::Little)
+    /// pattern:    00 10 40 00
+    /// ```
+    pub fn from_u32(value: u32, endian: Endian) -> Self {
+        Self::from_parts(endian.u32_bytes(value).to_vec(), vec![true; 4])
+    }
+
+    /// Creates a pattern builder from a sequence of optional bytes.<br><br>
+    ///
+    /// Each `Some(byte)` represents a fixed byte, and each `None` represents a
+    /// wildcard byte.<br>
+    /// This is the most natural representation for patterns that are generated
+    /// programmatically, or produced by other parsers.<br><br>
+    ///
+    /// # Arguments
+    /// * `iter` - The optional bytes of the pattern.
+    ///
+    /// # Returns
+    /// The current instance of the builder, or `None` if the parameters are invalid.<br><br>
+    ///
+    /// # Errors
+    /// * `BuilderError::InvalidSignature` - The sequence is empty.
+    ///
+    /// # Format
+    /// ```ignore
+    /// iter:       [Some(0x48), Some(0x8B), None, None]
+    /// ```
+    pub fn from_iter_opt(iter: impl IntoIterator<Item = Option<u8>>) -> Result<Self, BuilderError> {
+        let (signature_bytes, mask_bytes): (Vec<u8>, Vec<bool>) = iter.into_iter()
+            .map(|byte| (byte.unwrap_or(0), byte.is_some()))
+            .unzip();
+
+        if signature_bytes.is_empty() {
+            Err(BuilderError::InvalidSignature(
+                "the pattern cannot be empty".to_string()
+            ))?
+        }
+
+        Ok(Self::from_parts(signature_bytes, mask_bytes))
+    }
+
     /// Creates a pattern builder from an IDA-style signature.<br><br>
     ///
     /// An IDA-style signature is characterized by a single string of hexadecimal
     /// values separated by spaces.<br>
-    /// In this string, you can use `?` or `??` to represent a wildcard byte.<br><br>
+    /// In this string, you can use `?` or `??` to represent a wildcard byte, and enclose
+    /// a byte in parentheses to make it optional, so that the matches may either contain
+    /// or omit it. (e.g. `(48)` for an optional REX prefix)<br><br>
     ///
     /// It is generally preferred as it is shorter and easier to read, but it may
     /// introduce some overhead as it is ultimately converted to a code-style like AOB.<br><br>
@@ -160,12 +629,14 @@ impl PatternBuilder {
     /// The current instance of the builder, or `None` if the parameters are invalid.<br><br>
     ///
     /// # Errors
-    /// * `BuilderError::InvalidSignature` - The pattern string is empty.
-    /// * `BuilderError::ParseError` - The pattern string contains invalid hexadecimal values.
+    /// * `BuilderError::InvalidSignature` - The pattern string is empty, or all of its
+    ///   fixed bytes are optional.
+    /// * `BuilderError::ParseAt` - The pattern string contains invalid hexadecimal values.
     ///
     /// # Format
     /// ```ignore
     /// pattern:    "48 8B 05 ? ? ? ?" // or "48 8B 05 ?? ?? ?? ??"
+    /// optional:   "(48) 8B 05 ? ? ? ?"
     /// ```
     pub fn from_ida_style(pattern: &str) -> Result<Self, BuilderError> {
         if pattern.is_empty() {
@@ -176,24 +647,86 @@ impl PatternBuilder {
 
         let mut signature_bytes: Vec<u8> = vec![];
         let mut mask_bytes: Vec<bool> = vec![];
+        let mut optional = vec![];
 
-        for pair in pattern.split_whitespace() {
+        for (index, pair) in tokenize(pattern) {
             if pair == "?" || pair == "??" {
                 mask_bytes.push(false);
                 signature_bytes.push(0);
+            } else if let Some(byte) = pair.strip_prefix('(').and_then(|pair| pair.strip_suffix(')')) {
+                optional.push(signature_bytes.len());
+                mask_bytes.push(true);
+                signature_bytes.push(
+                    parse_byte(byte, index).map_err(|_| BuilderError::ParseAt {
+                        index,
+                        token: pair.to_string(),
+                    })?
+                );
             } else {
                 mask_bytes.push(true);
                 signature_bytes.push(
-                    u8::from_str_radix(pair, 16)?
+                    parse_byte(pair, index)?
                 );
             }
         }
 
-        Ok(Self {
-            signature: signature_bytes,
-            mask: mask_bytes,
-            threads: 1,
-        })
+        // The matches that omit all the optional bytes must contain at least a fixed byte.
+        if !optional.is_empty() && mask_bytes.iter().filter(|&&fixed| fixed).count() == optional.len() {
+            Err(BuilderError::InvalidSignature(
+                "the pattern must contain a fixed byte that is not optional".to_string()
+            ))?
+        }
+
+        let mut builder = Self::from_parts(signature_bytes, mask_bytes);
+        builder.optional = optional;
+        Ok(builder)
+    }
+
+    /// Creates a pattern builder from a Cheat Engine AOB signature.<br><br>
+    ///
+    /// A Cheat Engine signature is characterized by a single string of two-digit
+    /// hexadecimal values separated by spaces.<br>
+    /// In this string, you can use `*` or `**` to represent a wildcard byte.<br><br>
+    ///
+    /// # Arguments
+    /// * `pattern` - The Cheat Engine pattern string.
+    ///
+    /// # Returns
+    /// The current instance of the builder, or `None` if the parameters are invalid.<br><br>
+    ///
+    /// # Errors
+    /// * `BuilderError::InvalidSignature` - The pattern string is empty.
+    /// * `BuilderError::ParseAt` - The pattern string contains an invalid token.
+    ///
+    /// # Format
+    /// ```ignore
+    /// pattern:    "48 8B 05 * * * *" // or "48 8B 05 ** ** ** **"
+    /// ```
+    pub fn from_cheat_engine(pattern: &str) -> Result<Self, BuilderError> {
+        if pattern.trim().is_empty() {
+            Err(BuilderError::InvalidSignature(
+                "the pattern cannot be empty".to_string()
+            ))?
+        }
+
+        let mut signature_bytes: Vec<u8> = vec![];
+        let mut mask_bytes: Vec<bool> = vec![];
+
+        for (index, token) in tokenize(pattern) {
+            if token == "*" || token == "**" {
+                mask_bytes.push(false);
+                signature_bytes.push(0);
+            } else if token.len() == 2 {
+                mask_bytes.push(true);
+                signature_bytes.push(
+                    parse_byte(token, index)?
+                );
+            } else {
+                Err(BuilderError::ParseAt { index, token: token.to_string() })?
+            }
+        }
+
+        Ok(Self::from_parts(signature_bytes, mask_bytes))
     }
 
     /// Creates a pattern builder from a string of non-spaced, case-insensitive hex bytes.<br><br>
@@ -209,8 +742,8 @@ impl PatternBuilder {
     /// The current instance of the builder, or `None` if the parameter is invalid.<br><br>
     ///
     /// # Errors
-    /// * `BuilderError::InvalidSignature` - The pattern is empty, its length is odd, contains invalid characters or single-char wildcards.
-    /// * `BuilderError::ParseError` - The pattern contains invalid hexadecimal characters.
+    /// * `BuilderError::InvalidSignature` - The pattern is empty, its length is odd or contains single-char wildcards.
+    /// * `BuilderError::ParseAt` - The pattern contains invalid hexadecimal characters.
     ///
     /// # Format
     /// ```ignore
@@ -224,7 +757,7 @@ impl PatternBuilder {
         }
 
         // A hex string must have an even number of characters.
-        if pattern.len() % 2 != 0 {
+        if !pattern.len().is_multiple_of(2) {
             Err(BuilderError::InvalidSignature(
                 "the pattern must have an even number of characters".to_string()
             ))?
@@ -233,7 +766,10 @@ impl PatternBuilder {
         let mut signature_bytes: Vec<u8> = vec![];
         let mut mask_bytes: Vec<bool> = vec![];
 
-        for pair in pattern.as_bytes().chunks(2) {
+        for (index, pair) in pattern.as_bytes().chunks(2).enumerate() {
+            // Index of the pair in the pattern string.
+            let index = index * 2;
+
             if pair == b"??" {
                 mask_bytes.push(false);
                 signature_bytes.push(0);
@@ -248,21 +784,74 @@ impl PatternBuilder {
                 match std::str::from_utf8(pair) {
                     Ok(pair) => {
                         signature_bytes.push(
-                            u8::from_str_radix(pair, 16)?
+                            parse_byte(pair, index)?
                         );
                     }
-                    Err(_) => Err(BuilderError::InvalidSignature(
-                        "the pattern contains an invalid character".to_string()
-                    ))?
+                    Err(_) => Err(BuilderError::ParseAt {
+                        index,
+                        token: String::from_utf8_lossy(pair).to_string(),
+                    })?
                 }
             }
         }
 
-        Ok(Self {
-            signature: signature_bytes,
-            mask: mask_bytes,
-            threads: 1,
-        })
+        Ok(Self::from_parts(signature_bytes, mask_bytes))
+    }
+
+    /// Creates a pattern builder that matches the ASCII representation of a hex string,
+    /// regardless of the case of its letters.<br><br>
+    ///
+    /// This is useful for buffers that store hex-encoded text, such as hashes or keys
+    /// in configuration files, where the same value may be written in upper or lower case.<br>
+    /// Unlike [`from_hex_string`](#method.from_hex_string), the pattern does not match the
+    /// raw bytes described by the string, but the characters of the string itself.<br>
+    /// In this string, you can use `?` to represent any character.<br><br>
+    ///
+    /// # Arguments
+    /// * `pattern` - The hex string to search for.
+    ///
+    /// # Returns
+    /// The current instance of the builder, or `None` if the parameter is invalid.<br><br>
+    ///
+    /// # Errors
+    /// * `BuilderError::InvalidSignature` - The pattern is empty.
+    /// * `BuilderError::ParseAt` - The pattern contains a non-hexadecimal character.
+    ///
+    /// # Format
+    /// ```ignore
+    /// pattern:    "DEADBEEF" // matches "DEADBEEF", "deadbeef", "DeAdBeEf", ...
+    /// ```
+    pub fn from_hex_ascii_ci(pattern: &str) -> Result<Self, BuilderError> {
+        if pattern.is_empty() {
+            Err(BuilderError::InvalidSignature(
+                "the pattern cannot be empty".to_string()
+            ))?
+        }
+
+        let mut signature_bytes: Vec<u8> = vec![];
+        let mut mask_bytes: Vec<u8> = vec![];
+
+        for (index, c) in pattern.char_indices() {
+            match c {
+                '?' => {
+                    mask_bytes.push(0x00);
+                    signature_bytes.push(0);
+                }
+                // Upper and lower case ASCII letters only differ by the bit 0x20,
+                // so it is excluded from the comparison.
+                'a'..='f' | 'A'..='F' => {
+                    mask_bytes.push(!0x20);
+                    signature_bytes.push(c as u8);
+                }
+                '0'..='9' => {
+                    mask_bytes.push(0xFF);
+                    signature_bytes.push(c as u8);
+                }
+                _ => Err(BuilderError::ParseAt { index, token: c.to_string() })?
+            }
+        }
+
+        Ok(Self::from_bit_parts(signature_bytes, mask_bytes))
     }
 
     /// Sets the number of threads to use for scanning.<br>
@@ -279,6 +868,7 @@ impl PatternBuilder {
             Err(BuilderError::InvalidThreadCount)
         } else {
             self.threads = threads;
+            self.auto_threads = false;
             Ok(self)
         }
     }
@@ -289,6 +879,119 @@ impl PatternBuilder {
     /// The current instance of the builder.
     pub fn with_all_threads(mut self) -> Self {
         self.threads = num_cpus::get();
+        self.auto_threads = false;
+        self
+    }
+
+    /// Defers the choice of the number of threads to the time of each scan.<br>
+    /// Buffers smaller than 64 KB are scanned with a single thread, as spawning the
+    /// workers would take longer than the scan itself, and larger buffers are scanned
+    /// with a thread for each 64 KB of data, up to the number of logical CPU cores.<br><br>
+    ///
+    /// # Returns
+    /// The current instance of the builder.
+    pub fn with_auto_threads(mut self) -> Self {
+        self.threads = num_cpus::get();
+        self.auto_threads = true;
+        self
+    }
+
+    /// Adds an exclusion to the pattern.<br>
+    /// A match is rejected if the byte at the given offset from the start of the
+    /// match is equal to the specified byte.<br><br>
+    ///
+    /// Negative offsets look behind the match, and offsets that fall outside of
+    /// the scanned data never reject a match.<br>
+    /// This function can be called multiple times to add more exclusions.<br><br>
+    ///
+    /// # Arguments
+    /// * `relative_offset` - The offset of the byte to check, relative to the start of the match.
+    /// * `byte` - The value that rejects the match.
+    ///
+    /// # Returns
+    /// The current instance of the builder.
+    pub fn with_exclusion(mut self, relative_offset: isize, byte: u8) -> Self {
+        self.exclusions.push((relative_offset, byte));
+        self
+    }
+
+    /// Adds a checksum constraint to the pattern.<br>
+    /// A match is only accepted if the CRC32 of the bytes in the given range, relative
+    /// to the start of the match, is equal to the expected value.<br><br>
+    ///
+    /// The range can extend past the end of the signature, to verify the data that
+    /// follows the match. Ranges that fall outside of the scanned data always reject
+    /// the match.<br>
+    /// This function can be called multiple times to add more checksums.<br><br>
+    ///
+    /// # Arguments
+    /// * `range` - The range of the bytes to verify, relative to the start of the match.
+    /// * `expected_crc32` - The CRC32 (IEEE) of the bytes in the range.
+    ///
+    /// # Returns
+    /// The current instance of the builder.
+    #[cfg(feature = "checksum")]
+    pub fn with_checksum(mut self, range: Range<usize>, expected_crc32: u32) -> Self {
+        self.checksums.push((range, expected_crc32));
+        self
+    }
+
+    /// Sets whether the scan should report matches that overlap with a previous match.<br>
+    /// This is enabled by default.<br><br>
+    ///
+    /// When disabled, after a match is found, the scan resumes from the first byte
+    /// after the match, so no match can start within the span of the previous one.<br>
+    /// In multi-threaded scans, the matches are collected by all the threads first, and
+    /// then reported in ascending order on the calling thread, so that the results are
+    /// the same as in single-threaded scans.<br><br>
+    ///
+    /// # Arguments
+    /// * `allow` - Whether overlapping matches should be reported.
+    ///
+    /// # Returns
+    /// The current instance of the builder.
+    pub fn with_overlapping(mut self, allow: bool) -> Self {
+        self.overlapping = allow;
+        self
+    }
+
+    /// Sets the minimum distance between the reported matches.<br>
+    /// This is `0` by default, which reports all the matches.<br><br>
+    ///
+    /// After a match is reported, all the matches that start less than `distance`
+    /// bytes after it are suppressed, so that repetitive data produces a single
+    /// report per region.<br>
+    /// In multi-threaded scans, this is best-effort, as each thread only tracks the
+    /// matches in its own chunk of data, so matches near the chunk boundaries may
+    /// not be suppressed.<br><br>
+    ///
+    /// # Arguments
+    /// * `distance` - The minimum distance in bytes between two reported matches.
+    ///
+    /// # Returns
+    /// The current instance of the builder.
+    pub fn with_min_distance(mut self, distance: usize) -> Self {
+        self.min_distance = distance;
+        self
+    }
+
+    /// Sets whether the scan should check two anchor bytes before comparing the whole signature.<br>
+    /// This is enabled by default.<br><br>
+    ///
+    /// When enabled, the two rarest fixed bytes of the signature are used as anchors,
+    /// which greatly reduces the number of full comparisons for wildcard-heavy patterns.<br>
+    /// The longest run of fixed bytes is also searched as a whole before the anchors,
+    /// if it contains at least two bytes.<br>
+    /// When disabled, only the first byte of the signature is used as anchor.<br>
+    /// This does not affect which matches are reported.<br><br>
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to use two anchors selected by their rarity.
+    ///
+    /// # Returns
+    /// The current instance of the builder.
+    pub fn with_dual_anchor(mut self, enabled: bool) -> Self {
+        self.dual_anchor = enabled;
         self
     }
 
@@ -297,6 +1000,48 @@ impl PatternBuilder {
     /// # Returns
     /// The created pattern instance.
     pub fn build(self) -> Pattern {
-        Pattern::new(self.signature, self.mask, self.threads)
+        let mut pattern = Pattern::from_bit_masks(self.signature, self.mask, self.threads);
+        pattern.exclusions = self.exclusions;
+        pattern.checksums = self.checksums;
+        pattern.overlapping = self.overlapping;
+        pattern.min_distance = self.min_distance;
+        pattern.auto_threads = self.auto_threads;
+        pattern.set_optional(&self.optional);
+        if !self.dual_anchor {
+            pattern.select_anchors(false);
+        }
+        pattern
     }
 }
+
+/// Internal function that splits a pattern string into whitespace-separated tokens.<br><br>
+///
+/// # Arguments
+/// * `pattern` - The pattern string.
+///
+/// # Returns
+/// An iterator over the tokens and their byte index in the pattern string.
+fn tokenize(pattern: &str) -> impl Iterator<Item = (usize, &str)> {
+    // The tokens are subslices of the pattern, so their index is the
+    // distance between the start of the token and the start of the pattern.
+    pattern.split_whitespace()
+        .map(move |token| (token.as_ptr() as usize - pattern.as_ptr() as usize, token))
+}
+
+/// Internal function that parses a token of a pattern string as a hexadecimal byte.<br><br>
+///
+/// # Arguments
+/// * `token` - The token to parse.
+/// * `index` - The byte index of the token in the pattern string. (used in the error)
+///
+/// # Returns
+/// The parsed byte, or `BuilderError::ParseAt` if the token is not a valid byte.
+fn parse_byte(token: &str, index: usize) -> Result<u8, BuilderError> {
+    // `from_str_radix` also accepts a leading sign, which is not valid in a pattern.
+    if !token.chars().all(|c| c.is_ascii_hexdigit()) {
+        Err(BuilderError::ParseAt { index, token: token.to_string() })?
+    }
+
+    u8::from_str_radix(token, 16)
+        .map_err(|_| BuilderError::ParseAt { index, token: token.to_string() })
+}
@@ -0,0 +1,70 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::{BuilderError, Pattern, PatternBuilder};
+
+/// Maximum number of patterns kept by [`compile_cached`].
+const CACHE_CAPACITY: usize = 256;
+
+/// The compiled patterns, and the tick of the call that last used each of them.
+#[derive(Default)]
+struct PatternCache {
+    patterns: HashMap<String, (Arc<Pattern>, u64)>,
+    tick: u64,
+}
+
+/// Builds an IDA-style pattern, reusing the pattern built by a previous call with
+/// the same string.<br><br>
+///
+/// This is useful in hot loops, as the pattern string is only parsed once, and all
+/// the calls share the same pattern instance.<br>
+/// The patterns are built with the default settings, and the cache holds up to 256
+/// of them. When it is full, the least recently used pattern is discarded.<br>
+/// The cache is shared by all the threads.<br><br>
+///
+/// # Arguments
+/// * `pattern` - The IDA-style pattern string.
+///
+/// # Returns
+/// The shared pattern instance.<br><br>
+///
+/// # Errors
+/// Any error of [`PatternBuilder::from_ida_style`](struct.PatternBuilder.html#method.from_ida_style).
+/// Invalid patterns are not cached.
+///
+/// # Example
+/// ```
+/// let first = aobscan::compile_cached("48 8B ? ? 90").unwrap();
+/// let second = aobscan::compile_cached("48 8B ? ? 90").unwrap();
+/// assert!(std::sync::Arc::ptr_eq(&first, &second));
+/// ```
+pub fn compile_cached(pattern: &str) -> Result<Arc<Pattern>, BuilderError> {
+    static CACHE: OnceLock<Mutex<PatternCache>> = OnceLock::new();
+
+    // The cache only contains complete entries, so it can be used even if
+    // another thread panicked while holding the lock.
+    let mut cache = CACHE.get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    cache.tick += 1;
+    let tick = cache.tick;
+
+    if let Some((compiled, last_used)) = cache.patterns.get_mut(pattern) {
+        *last_used = tick;
+        return Ok(compiled.clone());
+    }
+
+    let compiled = Arc::new(PatternBuilder::from_ida_style(pattern)?.build());
+
+    if cache.patterns.len() >= CACHE_CAPACITY {
+        let oldest = cache.patterns.iter()
+            .min_by_key(|(_, (_, last_used))| *last_used)
+            .map(|(key, _)| key.clone());
+        if let Some(oldest) = oldest {
+            cache.patterns.remove(&oldest);
+        }
+    }
+
+    cache.patterns.insert(pattern.to_string(), (compiled.clone(), tick));
+    Ok(compiled)
+}
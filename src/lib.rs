@@ -1,9 +1,36 @@
-pub use builder::{BuilderError, PatternBuilder};
+pub use bits::scan_bits;
+pub use builder::{BuilderError, convert, detect_and_parse, Endian, load_sig_db, Mask, pattern_from_diff, PatternBuilder, PatternStyle, Signature};
+pub use cache::compile_cached;
 #[cfg(feature = "object-scan")]
-pub use object_scan::{ObjectError, ObjectScan, SectionResult};
-pub use pattern::Pattern;
+pub use object_scan::{ArchKey, dedup_by_content, is_object_file, object_kind, ObjectError, ObjectKind, ObjectScan, SectionResult, SectionScanner};
+pub use output::{IdaScriptAction, matches_to_ida_script, OutputFormat, rle_to_offsets};
+#[cfg(feature = "serde")]
+pub use output::matches_to_json;
+pub use pattern::{Match, MatchKey, Pattern, ScanBuffer, ScanControl, ScanError};
+pub use pointer::{scan_ptr32, scan_ptr_range32};
+pub use report::ScanReport;
+pub use sink::{CountSink, FirstSink, MatchSink, VecSink};
+pub use step::StepScanner;
+pub use stream::StreamScanner;
+pub use struct_builder::StructPatternBuilder;
+pub use timeline::scan_timeline;
 
+mod bits;
 mod builder;
+mod cache;
 #[cfg(feature = "object-scan")]
 mod object_scan;
+mod output;
 mod pattern;
+mod pointer;
+pub mod prelude;
+mod report;
+#[cfg(all(feature = "sse2", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+mod simd;
+mod sink;
+mod step;
+mod stream;
+mod struct_builder;
+#[cfg(feature = "testing")]
+mod testing;
+mod timeline;
@@ -1,17 +1,30 @@
 use object::{
+    Architecture,
+    BinaryFormat,
+    File,
+    FileKind,
     macho::FatHeader,
     Object,
     ObjectSection,
+    ObjectSymbol,
     read::macho::FatArch,
     Section,
+    SectionKind,
 };
 
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
 use crate::Pattern;
 
 /// Information about a match found by the scanner in a section of an object file.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SectionResult {
-    /// The offset of the match in the raw data slice. (archive offset + section offset)
+    /// The offset of the match in the raw data slice. (archive offset + section offset)<br>
+    /// This is the position to seek to in the original file to read or patch the
+    /// matched bytes, also for the matches in the slices of FAT archives.
     pub raw_offset: usize,
 
     /// The offset of the match in the specified section. (section address + match offset)
@@ -20,6 +33,13 @@ pub struct SectionResult {
     /// The base address of the specified section.
     pub section_address: u64,
 
+    /// The name of the section containing the match.
+    pub section_name: String,
+
+    /// The name of the segment containing the section of the match. (e.g. `__TEXT`)<br>
+    /// This is only available for Mach-O binaries, and is `None` for the other formats.
+    pub segment_name: Option<String>,
+
     /// An identifier for the archive containing the value.<br><br>
     ///
     /// # Values
@@ -28,6 +48,108 @@ pub struct SectionResult {
     pub archive_id: Option<String>,
 }
 
+/// Identifies the object file of the matches collected by
+/// [`ObjectScan::scan_object_by_arch`](trait.ObjectScan.html#tymethod.scan_object_by_arch).<br><br>
+///
+/// The slices of a FAT archive may have the same architecture (such as `arm64` and
+/// `arm64e`), so they are identified by their index in the archive as well.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ArchKey {
+    /// The index of the slice in the FAT archive, or `None` for normal binaries.
+    pub slice: Option<usize>,
+
+    /// The architecture of the object file.
+    pub architecture: Architecture,
+
+    /// The CPU subtype of the slice in the FAT archive, or `None` for normal binaries.
+    pub cpu_subtype: Option<u32>,
+}
+
+/// An object file parsed from the scanned data, with the identifier of the archive
+/// containing it, the offset to the archive and the key of its architecture.
+type ParsedObject<'a> = (File<'a>, Option<String>, usize, ArchKey);
+
+/// The formats of object files that can be detected by [`object_kind`](fn.object_kind.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectKind {
+    /// ELF file. (32-bit or 64-bit)
+    Elf,
+    /// THIN Mach-O file. (32-bit or 64-bit)
+    MachO,
+    /// FAT Mach-O archive, containing a THIN Mach-O file for each architecture.
+    MachOFat,
+    /// PE file. (32-bit or 64-bit)
+    Pe,
+    /// COFF object file.
+    Coff,
+    /// Unix `ar` archive, such as a static library.
+    Archive,
+    /// WebAssembly module.
+    Wasm,
+}
+
+/// Detects the format of an object file from its magic bytes.<br><br>
+///
+/// This only inspects the header of the data, so a detected format does not
+/// guarantee that the rest of the file is valid.<br><br>
+///
+/// # Arguments
+/// * `data` - The data slice to inspect.
+///
+/// # Returns
+/// The format of the object file, or `None` if the format is unknown.
+pub fn object_kind(data: &[u8]) -> Option<ObjectKind> {
+    match FileKind::parse(data).ok()? {
+        FileKind::Elf32 | FileKind::Elf64 => Some(ObjectKind::Elf),
+        FileKind::MachO32 | FileKind::MachO64 => Some(ObjectKind::MachO),
+        FileKind::MachOFat32 | FileKind::MachOFat64 => Some(ObjectKind::MachOFat),
+        FileKind::Pe32 | FileKind::Pe64 => Some(ObjectKind::Pe),
+        FileKind::Coff => Some(ObjectKind::Coff),
+        FileKind::Archive => Some(ObjectKind::Archive),
+        FileKind::Wasm => Some(ObjectKind::Wasm),
+        _ => None,
+    }
+}
+
+/// Checks whether the data is an object file that can be scanned with [`ObjectScan`].<br><br>
+///
+/// Unlike [`object_kind`](fn.object_kind.html), this parses the headers of the file
+/// the same way as the scan functions do, so archives, which can't be scanned, are
+/// not considered object files.<br><br>
+///
+/// # Arguments
+/// * `data` - The data slice to inspect.
+///
+/// # Returns
+/// True if the data can be scanned with [`ObjectScan`], otherwise false.
+pub fn is_object_file(data: &[u8]) -> bool {
+    File::parse(data).is_ok() || FatHeader::parse_arch32(data).is_ok()
+}
+
+/// Removes the results whose matched bytes are the same as the ones of a previous result.<br><br>
+///
+/// This is useful to collapse the matches of the same code in the slices of a FAT archive
+/// into a single representative result.<br>
+/// The results whose bytes are not entirely contained in the data are always kept.<br><br>
+///
+/// # Arguments
+/// * `data` - The data slice that was scanned.
+/// * `matches` - The results of the scan.
+/// * `len` - The number of bytes of each match to compare, starting from its `raw_offset`.
+///
+/// # Returns
+/// The first result of each distinct byte sequence, in the order of the input.
+pub fn dedup_by_content(data: &[u8], matches: &[SectionResult], len: usize) -> Vec<SectionResult> {
+    let mut seen = HashSet::new();
+    matches.iter()
+        .filter(|result| {
+            result.raw_offset.checked_add(len)
+                .and_then(|end| data.get(result.raw_offset..end))
+                .is_none_or(|bytes| seen.insert(bytes))
+        })
+        .cloned()
+        .collect()
+}
 
 /// An error in the object pattern scanner.<br>
 /// This encapsulates all possible errors that can occur when scanning for
@@ -40,6 +162,8 @@ pub enum ObjectError {
     SectionNotFound,
     /// Thrown when the data of the specified binary section is not available.
     SectionDataNotFound,
+    /// Thrown when the specified symbol is not found, or it is not defined in a section.
+    SymbolNotFound,
 }
 
 impl std::fmt::Display for ObjectError {
@@ -56,6 +180,7 @@ impl std::fmt::Display for ObjectError {
             Self::InvalidObject => write!(f, "the content of the data to scan is not a valid object file"),
             Self::SectionNotFound => write!(f, "the specified binary section is not found"),
             Self::SectionDataNotFound => write!(f, "the data of the specified binary section is not available"),
+            Self::SymbolNotFound => write!(f, "the specified symbol is not found"),
         }
     }
 }
@@ -76,9 +201,17 @@ pub trait ObjectScan {
     ///
     /// FAT Mach-O binaries are also supported, and in this case all the THIN binaries
     /// are scanned for the given section.<br>
+    /// The THIN binaries are scanned in parallel, each with the thread count of the
+    /// pattern, so the callback may receive their matches in any order.<br>
     /// Information about which THIN binary contains the match is returned in
     /// the callback.<br><br>
     ///
+    /// WebAssembly modules are supported as well. Their standard sections can be
+    /// referenced either by their plain name (e.g. `code`) or by the name used by
+    /// the `object` crate (e.g. `<code>`).<br>
+    /// Wasm sections are not mapped in memory, so the section address is always `0`
+    /// and the section offset is relative to the start of the section's payload.<br><br>
+    ///
     /// # Arguments
     /// * `data` - The data slice to scan.
     /// * `section_name` - The name of the section to scan. (e.g. `__text`)
@@ -95,6 +228,127 @@ pub trait ObjectScan {
         section_name: &str,
         callback: impl FnMut(SectionResult) -> bool + Send + Sync,
     ) -> Result<bool, ObjectError>;
+
+    /// Performs the AOB scan in the specified object section of the given slice, passing
+    /// the data of the section to the callback along with each match.<br><br>
+    ///
+    /// This works like [`scan_object`](#tymethod.scan_object), and is useful to inspect
+    /// or patch the bytes around the matches without fetching the section data again.<br>
+    /// The slice is the raw data of the section in the file, so the `section_offset` of
+    /// the match can be used to index it. (compressed sections are not decompressed)<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `section_name` - The name of the section to scan. (e.g. `__text`)
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives a structure containing all the information of the match
+    ///      and the data of the section containing it as arguments.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// Ok(true) if at least one match was found, Ok(false) if no matches were found,
+    /// Err if an error occurred.
+    fn scan_object_with_data(
+        &self,
+        data: &[u8],
+        section_name: &str,
+        callback: impl FnMut(SectionResult, &[u8]) -> bool + Send + Sync,
+    ) -> Result<bool, ObjectError>;
+
+    /// Performs the AOB scan in the specified object section of the given slice, and
+    /// collects the matches of each architecture.<br><br>
+    ///
+    /// This is mostly useful for FAT Mach-O binaries, in which each THIN binary is
+    /// scanned separately.<br>
+    /// Each binary is identified by its architecture, and by its index and CPU subtype
+    /// if it is a slice of a FAT archive, so that no two binaries share the same key.<br>
+    /// Architectures whose binary contains the section but no matches are mapped to
+    /// an empty list, while the ones whose binary does not contain the section are omitted.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `section_name` - The name of the section to scan. (e.g. `__text`)
+    ///
+    /// # Returns
+    /// Ok with the matches of each architecture, sorted by offset in ascending order,
+    /// or Err if an error occurred.
+    fn scan_object_by_arch(
+        &self,
+        data: &[u8],
+        section_name: &str,
+    ) -> Result<HashMap<ArchKey, Vec<SectionResult>>, ObjectError>;
+
+    /// Performs the AOB scan in the specified section of an already parsed object file.<br><br>
+    ///
+    /// This is useful to avoid parsing the same object file multiple times, when
+    /// scanning it for more than one pattern.<br>
+    /// As the file is a single object, the archive identifier of the results is always `None`.<br><br>
+    ///
+    /// # Arguments
+    /// * `file` - The parsed object file to scan.
+    /// * `section_name` - The name of the section to scan. (e.g. `__text`)
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives a structure containing all the information of the match as argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// Ok(true) if at least one match was found, Ok(false) if no matches were found,
+    /// Err if an error occurred.
+    fn scan_parsed<'a>(
+        &self,
+        file: &File<'a>,
+        section_name: &str,
+        callback: impl FnMut(SectionResult) -> bool + Send + Sync,
+    ) -> Result<bool, ObjectError>;
+
+    /// Performs the AOB scan in all the sections of the specified kind. (e.g. `SectionKind::Text`)<br><br>
+    ///
+    /// FAT Mach-O binaries are also supported, and in this case the sections of all
+    /// the THIN binaries are scanned.<br>
+    /// The name of the section containing each match is returned in the callback.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `kind` - The kind of the sections to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives a structure containing all the information of the match as argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// Ok(true) if at least one match was found, Ok(false) if no matches were found,
+    /// Err if an error occurred.
+    fn scan_sections_of_kind(
+        &self,
+        data: &[u8],
+        kind: SectionKind,
+        callback: impl FnMut(SectionResult) -> bool + Send + Sync,
+    ) -> Result<bool, ObjectError>;
+
+    /// Performs the AOB scan in the bytes of the specified symbol. (e.g. a function)<br><br>
+    ///
+    /// The bytes of the symbol are located with its address and size in the symbol table.<br>
+    /// If the size of the symbol is not known, as in COFF and Mach-O files, the symbol is
+    /// considered to extend up to the next symbol of its section, or to the end of the section.<br>
+    /// The name is compared as it appears in the symbol table, which in Mach-O files
+    /// includes the leading underscore. (e.g. `_main`)<br>
+    /// In FAT Mach-O binaries, the symbol of the first THIN binary that defines it is scanned.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `symbol_name` - The name of the symbol to scan. (case-sensitive)
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match, relative to the start of the symbol, as argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// Ok(true) if at least one match was found, Ok(false) if no matches were found,
+    /// Err if an error occurred.
+    fn scan_symbol(
+        &self,
+        data: &[u8],
+        symbol_name: &str,
+        callback: impl FnMut(usize) -> bool + Send + Sync,
+    ) -> Result<bool, ObjectError>;
 }
 
 impl ObjectScan for Pattern {
@@ -104,69 +358,407 @@ impl ObjectScan for Pattern {
         section_name: &str,
         mut callback: impl FnMut(SectionResult) -> bool + Send + Sync,
     ) -> Result<bool, ObjectError> {
-        // Different object file formats must be handled individually.
-        // For instance, Mach-O FAT files contain multiple architecture binaries,
-        // and we must scan the section in each one of them.
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "scan_object",
+            pattern = %self.to_ida_style(),
+            data_len = data.len(),
+            threads = self.get_threads(),
+            section = section_name,
+            matches = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+        #[cfg(feature = "tracing")]
+        let mut matches = 0usize;
+
+        let result = self.scan_object_with_data(data, section_name, |result, _| {
+            #[cfg(feature = "tracing")]
+            {
+                matches += 1;
+            }
+
+            callback(result)
+        });
+
+        #[cfg(feature = "tracing")]
+        span.record("matches", matches);
+        result
+    }
+
+    fn scan_object_with_data(
+        &self,
+        data: &[u8],
+        section_name: &str,
+        callback: impl FnMut(SectionResult, &[u8]) -> bool + Send + Sync,
+    ) -> Result<bool, ObjectError> {
+        scan_objects(self, &parse_objects(data)?, section_name, callback)
+    }
+
+    fn scan_object_by_arch(
+        &self,
+        data: &[u8],
+        section_name: &str,
+    ) -> Result<HashMap<ArchKey, Vec<SectionResult>>, ObjectError> {
+        let mut results = HashMap::new();
 
-        // Normal binary files only containing one architecture.
-        if let Ok(file) = object::File::parse(data) {
+        for (file, archive_id, archive_offset, key) in parse_objects(data)? {
             // Find the section with the specified name. (name is case-sensitive)
-            let section = file.section_by_name(section_name)
-                .ok_or(ObjectError::SectionNotFound)?;
+            let section = match find_section(&file, section_name) {
+                Some(section) => section,
+                None => continue,
+            };
+
+            let mut matches = vec![];
+            scan_section(self, &section, archive_id, archive_offset, &mut |result, _| {
+                matches.push(result);
+                true
+            }, &AtomicBool::new(false))?;
 
-            // Perform the scan in the section.
-            scan_section(&self, &section, None, 0, &mut callback)
+            // Multi-threaded scans report the matches in no particular order.
+            matches.sort_by_key(|result| result.raw_offset);
+            results.insert(key, matches);
         }
-        // Mach-O FAT archives.
-        else if let Ok(archive) = FatHeader::parse_arch32(&*data) {
-            let mut section_found = false;
-            let mut found = false;
-
-            // Iterate over the THIN binaries in the FAT file.
-            for arch in archive {
-                // Get the data slice of the THIN binary.
-                if let Ok(data) = arch.data(&*data) {
-                    // Parse the object file.
-                    let file = object::File::parse(data)
-                        .or(Err(ObjectError::InvalidObject))?;
-
-                    // Find the section with the specified name.
-                    if let Some(section) = file.section_by_name(section_name) {
-                        section_found = true;
-
-                        // Perform the scan in the section.
-                        if scan_section(
-                            &self,
-                            &section,
-                            Some(format!("{:#?}", arch.architecture())),
-                            arch.offset() as usize,
-                            &mut callback,
-                        )? {
-                            found = true;
-                        }
-                    }
+
+        if results.is_empty() {
+            // If the section was not found in any of the object files, return an error.
+            Err(ObjectError::SectionNotFound)
+        } else {
+            Ok(results)
+        }
+    }
+
+    fn scan_parsed<'a>(
+        &self,
+        file: &File<'a>,
+        section_name: &str,
+        mut callback: impl FnMut(SectionResult) -> bool + Send + Sync,
+    ) -> Result<bool, ObjectError> {
+        // Find the section with the specified name. (name is case-sensitive)
+        let section = find_section(file, section_name)
+            .ok_or(ObjectError::SectionNotFound)?;
+
+        // Perform the scan in the section.
+        scan_section(self, &section, None, 0, &mut |result, _| callback(result), &AtomicBool::new(false))
+    }
+
+    fn scan_sections_of_kind(
+        &self,
+        data: &[u8],
+        kind: SectionKind,
+        mut callback: impl FnMut(SectionResult) -> bool + Send + Sync,
+    ) -> Result<bool, ObjectError> {
+        let mut section_found = false;
+        let mut found = false;
+
+        // Scan the sections in each of the object files contained in the data.
+        'objects: for (file, archive_id, archive_offset, _) in parse_objects(data)? {
+            for section in file.sections().filter(|section| section.kind() == kind) {
+                // Perform the scan in the section.
+                section_found = true;
+                let stopped = AtomicBool::new(false);
+                found |= scan_section(
+                    self,
+                    &section,
+                    archive_id.clone(),
+                    archive_offset,
+                    &mut |result, _| callback(result),
+                    &stopped,
+                )?;
+
+                if stopped.load(Ordering::Relaxed) {
+                    break 'objects;
                 }
             }
+        }
+
+        if !section_found {
+            // If no section of the specified kind was found, return an error.
+            Err(ObjectError::SectionNotFound)
+        } else {
+            // Return true if at least one match was found.
+            Ok(found)
+        }
+    }
 
-            if !section_found {
-                // If the section was not found in any of the THIN binaries, return an error.
-                Err(ObjectError::SectionNotFound)
+    fn scan_symbol(
+        &self,
+        data: &[u8],
+        symbol_name: &str,
+        callback: impl FnMut(usize) -> bool + Send + Sync,
+    ) -> Result<bool, ObjectError> {
+        for (file, _, _, _) in parse_objects(data)? {
+            // Find the symbol with the specified name. (name is case-sensitive)
+            // Only the symbols defined in a section have bytes to scan.
+            let symbol = file.symbols()
+                .filter(|symbol| symbol.is_definition() && symbol.name() == Ok(symbol_name))
+                .find_map(|symbol| symbol.section_index().map(|index| (symbol, index)));
+            let (symbol, section_index) = match symbol {
+                Some(symbol) => symbol,
+                None => continue,
+            };
+
+            let section = file.section_by_index(section_index)
+                .or(Err(ObjectError::SectionNotFound))?;
+            let section_data = section.data()
+                .or(Err(ObjectError::SectionDataNotFound))?;
+
+            // The address of the symbol is in the same address space as the one of
+            // its section, so their difference is the offset of the symbol in the section.
+            let start = symbol.address().checked_sub(section.address())
+                .and_then(|offset| usize::try_from(offset).ok())
+                .ok_or(ObjectError::SectionDataNotFound)?;
+
+            // Some formats (such as COFF and Mach-O) do not store the size of the symbols, so
+            // in that case the symbol extends to the next symbol of the section, or to its end.
+            let end = if symbol.size() != 0 {
+                usize::try_from(symbol.size()).ok()
+                    .and_then(|size| start.checked_add(size))
+                    .ok_or(ObjectError::SectionDataNotFound)?
             } else {
-                // Return true if at least one match was found.
-                Ok(found)
-            }
+                file.symbols()
+                    .filter(|other| other.section_index() == Some(section_index))
+                    .filter_map(|other| other.address().checked_sub(section.address()))
+                    .filter_map(|offset| usize::try_from(offset).ok())
+                    .filter(|&offset| offset > start)
+                    .min()
+                    .unwrap_or(section_data.len())
+            };
+            let symbol_data = section_data.get(start..end)
+                .ok_or(ObjectError::SectionDataNotFound)?;
+
+            return Ok(self.scan(symbol_data, callback));
         }
-        // Invalid binary file format.
-        else {
-            Err(ObjectError::InvalidObject)
+
+        Err(ObjectError::SymbolNotFound)
+    }
+}
+
+
+/// A scanner for the sections of an object file, which is parsed a single time.<br><br>
+///
+/// This is useful to scan multiple sections of the same file, or the same section
+/// for multiple patterns, as [`ObjectScan::scan_object`](trait.ObjectScan.html#tymethod.scan_object)
+/// parses the file at every scan.<br>
+/// FAT Mach-O binaries are supported, and all of their THIN binaries are parsed when
+/// the scanner is created.<br><br>
+///
+/// # Example
+/// ```ignore
+/// let data = std::fs::read("macho_file").unwrap();
+/// let scanner = aobscan::SectionScanner::new(&data).unwrap();
+///
+/// scanner.scan(&pattern, "__text", |result| true).unwrap();
+/// scanner.scan(&pattern, "__stubs", |result| true).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct SectionScanner<'a> {
+    objects: Vec<ParsedObject<'a>>,
+}
+
+impl<'a> SectionScanner<'a> {
+    /// Parses the object files contained in the given slice.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The newly created scanner, or `ObjectError::InvalidObject` if the data
+    /// is not a valid object file.
+    pub fn new(data: &'a [u8]) -> Result<Self, ObjectError> {
+        Ok(Self {
+            objects: parse_objects(data)?,
+        })
+    }
+
+    /// Performs the AOB scan in the specified section of the parsed object files.<br><br>
+    ///
+    /// This works like [`ObjectScan::scan_object`](trait.ObjectScan.html#tymethod.scan_object),
+    /// without parsing the data again.<br><br>
+    ///
+    /// # Arguments
+    /// * `pattern` - The pattern to scan for.
+    /// * `section_name` - The name of the section to scan. (e.g. `__text`)
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives a structure containing all the information of the match as argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// Ok(true) if at least one match was found, Ok(false) if no matches were found,
+    /// Err if an error occurred.
+    pub fn scan(
+        &self,
+        pattern: &Pattern,
+        section_name: &str,
+        mut callback: impl FnMut(SectionResult) -> bool + Send + Sync,
+    ) -> Result<bool, ObjectError> {
+        scan_objects(pattern, &self.objects, section_name, |result, _| callback(result))
+    }
+}
+
+
+/// Internal function that parses the object files contained in the data slice.<br>
+/// Normal binaries contain a single object file, while Mach-O FAT archives
+/// contain an object file for each architecture.<br><br>
+///
+/// # Arguments
+/// * `data` - The data slice to parse.
+///
+/// # Returns
+/// Ok with the parsed files, together with the identifier of the archive containing
+/// them, the offset to the archive (see [`scan_section`](fn.scan_section.html)) and
+/// the key of their architecture, or Err if an error occurred.
+fn parse_objects(data: &[u8]) -> Result<Vec<ParsedObject<'_>>, ObjectError> {
+    // Different object file formats must be handled individually.
+    // For instance, Mach-O FAT files contain multiple architecture binaries,
+    // and we must handle each one of them.
+
+    // Normal binary files only containing one architecture.
+    if let Ok(file) = File::parse(data) {
+        let key = ArchKey {
+            slice: None,
+            architecture: file.architecture(),
+            cpu_subtype: None,
+        };
+        Ok(vec![(file, None, 0, key)])
+    }
+    // Mach-O FAT archives.
+    else if let Ok(archive) = FatHeader::parse_arch32(data) {
+        let mut objects = Vec::with_capacity(archive.len());
+
+        // Iterate over the THIN binaries in the FAT file.
+        for (index, arch) in archive.iter().enumerate() {
+            // Get the data slice of the THIN binary.
+            if let Ok(data) = arch.data(data) {
+                // Parse the object file.
+                let file = File::parse(data)
+                    .or(Err(ObjectError::InvalidObject))?;
+
+                let key = ArchKey {
+                    slice: Some(index),
+                    architecture: arch.architecture(),
+                    cpu_subtype: Some(arch.cpusubtype()),
+                };
+                objects.push((
+                    file,
+                    Some(format!("{:#?}", arch.architecture())),
+                    arch.offset() as usize,
+                    key,
+                ));
+            }
         }
+
+        Ok(objects)
+    }
+    // Invalid binary file format.
+    else {
+        Err(ObjectError::InvalidObject)
     }
 }
 
+/// Internal function that scans the section with the specified name in each of the
+/// parsed object files. (see [`ObjectScan::scan_object_with_data`](trait.ObjectScan.html#tymethod.scan_object_with_data))<br><br>
+///
+/// # Arguments
+/// * `pattern` - The pattern to scan for.
+/// * `objects` - The parsed object files. (see [`parse_objects`](fn.parse_objects.html))
+/// * `section_name` - The name of the section to scan. (e.g. `__text`)
+/// * `callback` - The callback to execute when a match is found.
+///
+/// # Returns
+/// Ok(true) if at least one match was found, Ok(false) if no matches were found,
+/// Err if an error occurred.
+fn scan_objects(
+    pattern: &Pattern,
+    objects: &[ParsedObject<'_>],
+    section_name: &str,
+    callback: impl FnMut(SectionResult, &[u8]) -> bool + Send + Sync,
+) -> Result<bool, ObjectError> {
+    // Find the section with the specified name in each of the object files
+    // contained in the data. (name is case-sensitive)
+    let sections = objects.iter()
+        .filter_map(|(file, archive_id, archive_offset, _)| {
+            find_section(file, section_name)
+                .map(|section| (section, archive_id, *archive_offset))
+        })
+        .collect::<Vec<_>>();
+
+    // If the section was not found in any of the object files, return an error.
+    let (first, others) = sections.split_first()
+        .ok_or(ObjectError::SectionNotFound)?;
+
+    // The sections of the THIN binaries in FAT archives are independent, so
+    // they are scanned in parallel, each with the thread count of the pattern.
+    // The callback is shared between the scans, so it must be synchronized.
+    // The stop flag is shared as well, so that when the callback stops the scan
+    // of a section, the scans of the other sections are stopped too.
+    let callback = Mutex::new(callback);
+    let stopped = AtomicBool::new(false);
+
+    let scan = |(section, archive_id, archive_offset): &(Section, &Option<String>, usize)| {
+        scan_section(pattern, section, (*archive_id).clone(), *archive_offset, &mut |result, section_data| {
+            // The callback mutex can only be poisoned if the callback panicked,
+            // and that panic is propagated when the threads are joined.
+            let mut callback = match callback.lock() {
+                Ok(callback) => callback,
+                Err(_) => return false,
+            };
+
+            // Do not run the callback if the scan of another section stopped it.
+            if stopped.load(Ordering::Relaxed) {
+                return false;
+            }
+
+            callback(result, section_data)
+        }, &stopped)
+    };
+
+    let results = thread::scope(|scope| {
+        // Scan the first section in the current thread, as normal binaries
+        // only contain one object file.
+        let handles = others.iter()
+            .map(|section| scope.spawn(|| scan(section)))
+            .collect::<Vec<_>>();
+
+        let mut results = vec![scan(first)];
+        results.extend(handles.into_iter().map(|handle| {
+            handle.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        }));
+        results
+    });
+
+    // Return true if at least one match was found.
+    results.into_iter().try_fold(false, |found, result| Ok(found | result?))
+}
+
+/// Internal function that looks up a section by name in a parsed object file.<br><br>
+///
+/// The standard sections of WebAssembly modules are named `<code>`, `<data>`, etc.
+/// by the `object` crate, so for this format the plain name is also accepted.<br><br>
+///
+/// # Arguments
+/// * `file` - The parsed object file.
+/// * `section_name` - The name of the section to find. (case-sensitive)
+///
+/// # Returns
+/// The section with the specified name, or `None` if it does not exist.
+fn find_section<'data, 'file>(
+    file: &'file File<'data>,
+    section_name: &str,
+) -> Option<Section<'data, 'file>> {
+    file.section_by_name(section_name).or_else(|| {
+        if file.format() == BinaryFormat::Wasm {
+            file.section_by_name(&format!("<{}>", section_name))
+        } else {
+            None
+        }
+    })
+}
 
 /// Internal function that scans a binary section for a pattern.<br>
 /// This function is used by both normal and FAT Mach-O binaries, and it
-/// is a wrapper around the normal Pattern::scan function.<br><br>
+/// is a wrapper around the Pattern::scan_stoppable function, which works like
+/// Pattern::scan but can be stopped by the scans of the other sections.<br><br>
 ///
 /// The callback is also wrapped to add other useful information to its
 /// arguments.<br><br>
@@ -177,6 +769,10 @@ impl ObjectScan for Pattern {
 ///   - Normal binaries should pass `None`.
 /// * `archive_offset` - The offset to the archive that contains the section. (used to calculate the absolute offset)
 /// * `callback` - The callback to execute when a match is found.
+///   - It also receives the data of the section.
+/// * `stopped` - The flag that stops the scan when set.
+///   - It is set to `true` when the callback stops the scan, so it can be shared
+///     to stop the scans of other sections.
 ///
 /// # Returns
 /// Ok(true) if at least one match was found, Ok(false) if no matches were found,
@@ -186,7 +782,8 @@ fn scan_section(
     section: &Section,
     archive_id: Option<String>,
     archive_offset: usize,
-    callback: &mut (impl FnMut(SectionResult) -> bool + Send + Sync),
+    callback: &mut (impl FnMut(SectionResult, &[u8]) -> bool + Send + Sync),
+    stopped: &AtomicBool,
 ) -> Result<bool, ObjectError> {
     // Get the data slice of the section.
     // This is the same as creating another slice from the data slice,
@@ -199,15 +796,20 @@ fn scan_section(
     let section_base = archive_offset + section.file_range()
         .ok_or(ObjectError::SectionDataNotFound)?.0 as usize;
 
+    let section_name = section.name().unwrap_or_default();
+    let segment_name = section.segment_name().ok().flatten().map(str::to_string);
+
     // Wrap the callback function to add another argument to it.
     // This allows us to pass both the section and file offset to the callback.
-    Ok(pattern.scan(section_data, |offset| {
+    Ok(pattern.scan_stoppable(section_data, stopped, |offset| {
         // Call the callback function with all the relevant data.
         callback(SectionResult {
             raw_offset: section_base + offset,
             section_offset: offset,
             section_address: section.address(),
+            section_name: section_name.to_string(),
+            segment_name: segment_name.clone(),
             archive_id: archive_id.clone(),
-        })
+        }, section_data)
     }))
 }
@@ -0,0 +1,216 @@
+use std::io::Write;
+
+use crate::Pattern;
+
+/// The format of the matches written by
+/// [`Pattern::scan_to_writer`](struct.Pattern.html#method.scan_to_writer).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutputFormat {
+    /// One decimal offset per line. (e.g. `4096`)
+    PlainOffsets,
+    /// One hexadecimal offset per line. (e.g. `0x1000`)
+    Hex,
+    /// One JSON object per line. (e.g. `{"offset":4096}`)
+    Json,
+}
+
+/// The action applied to each match by the script generated by
+/// [`matches_to_ida_script`](fn.matches_to_ida_script.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IdaScriptAction {
+    /// Sets a comment at the address. (e.g. `idc.set_cmt(0x1000, "comment", 0)`)
+    Comment,
+    /// Marks the address with a bookmark in the next slot. (e.g. `idc.put_bookmark(0x1000, 0, 0, 0, 1, "comment")`)
+    Bookmark,
+    /// Adds a breakpoint at the address. (e.g. `idc.add_bpt(0x1000)`)
+    Breakpoint,
+}
+
+impl Pattern {
+    /// Performs the AOB scan in the given slice, and writes each match to
+    /// the given writer, one per line.<br><br>
+    ///
+    /// The matches are written as soon as they are found, without collecting them.<br>
+    /// In multi-threaded scans, they are written in no particular order.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `out` - The writer to write the matches to.
+    /// * `format` - The format of each line.
+    ///
+    /// # Returns
+    /// The number of matches written, or the first error returned by the writer.
+    /// (in which case the scan is stopped)
+    pub fn scan_to_writer(
+        &self,
+        data: &[u8],
+        out: &mut (impl Write + Send + Sync),
+        format: OutputFormat,
+    ) -> std::io::Result<usize> {
+        let mut written = 0;
+        let mut error = None;
+
+        self.scan(data, |offset| {
+            let result = match format {
+                OutputFormat::PlainOffsets => writeln!(out, "{}", offset),
+                OutputFormat::Hex => writeln!(out, "{:#x}", offset),
+                OutputFormat::Json => writeln!(out, "{{\"offset\":{}}}", offset),
+            };
+
+            match result {
+                Ok(()) => {
+                    written += 1;
+                    true
+                }
+                Err(err) => {
+                    // Stop the scan at the first error.
+                    error = Some(err);
+                    false
+                }
+            }
+        });
+
+        match error {
+            Some(err) => Err(err),
+            None => Ok(written),
+        }
+    }
+
+    /// Performs the AOB scan in the given slice, and encodes the match offsets
+    /// as runs of consecutive offsets.<br><br>
+    ///
+    /// This is a compact representation of the matches when they are clustered,
+    /// such as the matches of a short pattern in a padding area.<br>
+    /// The offsets can be decoded with [`rle_to_offsets`](fn.rle_to_offsets.html).<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The runs of matches as `(start, length)` pairs, sorted by their start offset.
+    ///
+    /// # Example
+    /// ```
+    /// let pattern = aobscan::PatternBuilder::from_ida_style("CC")
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// let runs = pattern.scan_rle(b"\xCC\xCC\xCC\x90\xCC");
+    /// assert_eq!(runs, vec![(0, 3), (4, 1)]);
+    /// ```
+    pub fn scan_rle(&self, data: &[u8]) -> Vec<(usize, usize)> {
+        let mut runs: Vec<(usize, usize)> = vec![];
+        for offset in self.scan_all(data) {
+            match runs.last_mut() {
+                Some((start, length)) if *start + *length == offset => *length += 1,
+                _ => runs.push((offset, 1)),
+            }
+        }
+        runs
+    }
+}
+
+/// Exports the matches of a pattern as a JSON object.<br><br>
+///
+/// The object contains the IDA-style representation of the pattern and
+/// the offsets of the matches, as in:
+/// ```ignore
+/// {"pattern":"48 8B ? 90","offsets":[16,4096]}
+/// ```
+///
+/// # Arguments
+/// * `pattern` - The pattern that produced the matches.
+/// * `matches` - The offsets of the matches.
+///
+/// # Returns
+/// The JSON string.
+#[cfg(feature = "serde")]
+pub fn matches_to_json(pattern: &Pattern, matches: &[usize]) -> String {
+    serde_json::json!({
+        "pattern": pattern.to_ida_style(),
+        "offsets": matches,
+    }).to_string()
+}
+
+/// Exports the matches of a pattern as an IDAPython script.<br><br>
+///
+/// The script applies the chosen action at the address of each match, so the results
+/// of a scan can be pasted in the output window of IDA. The offsets are used as addresses
+/// as they are, so the matches should be relative to the image base of the database.
+/// (see [`Pattern::scan_based`](struct.Pattern.html#method.scan_based))<br><br>
+///
+/// # Arguments
+/// * `matches` - The addresses of the matches.
+/// * `comment` - The text of the comments and bookmarks. (ignored by breakpoints)
+/// * `action` - The action to apply to each match.
+///
+/// # Returns
+/// The script, with one line per match after the `import idc` line.
+///
+/// # Example
+/// ```
+/// use aobscan::IdaScriptAction;
+///
+/// let script = aobscan::matches_to_ida_script(&[0x1000], "found", IdaScriptAction::Comment);
+/// assert_eq!(script, "import idc\nidc.set_cmt(0x1000, \"found\", 0)\n");
+/// ```
+pub fn matches_to_ida_script(matches: &[usize], comment: &str, action: IdaScriptAction) -> String {
+    let comment = escape_python(comment);
+
+    let mut script = String::from("import idc\n");
+    for (index, address) in matches.iter().enumerate() {
+        let line = match action {
+            IdaScriptAction::Comment => format!("idc.set_cmt({:#x}, \"{}\", 0)", address, comment),
+            // The bookmark slots start from 1, as slot 0 is often used manually.
+            IdaScriptAction::Bookmark => format!(
+                "idc.put_bookmark({:#x}, 0, 0, 0, {}, \"{}\")",
+                address,
+                index + 1,
+                comment,
+            ),
+            IdaScriptAction::Breakpoint => format!("idc.add_bpt({:#x})", address),
+        };
+
+        script.push_str(&line);
+        script.push('\n');
+    }
+
+    script
+}
+
+/// Decodes the runs of matches returned by
+/// [`Pattern::scan_rle`](struct.Pattern.html#method.scan_rle) into their offsets.<br><br>
+///
+/// # Arguments
+/// * `runs` - The runs of matches as `(start, length)` pairs.
+///
+/// # Returns
+/// The offsets of the matches, in the order of the runs.
+pub fn rle_to_offsets(runs: &[(usize, usize)]) -> Vec<usize> {
+    runs.iter()
+        .flat_map(|&(start, length)| start..start + length)
+        .collect()
+}
+
+/// Internal function that escapes a string to be used in a double-quoted Python literal.<br><br>
+///
+/// # Arguments
+/// * `text` - The string to escape.
+///
+/// # Returns
+/// The escaped string, without the quotes.
+fn escape_python(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_control() => escaped.push_str(&format!("\\x{:02x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
@@ -1,9 +1,54 @@
-use std::ops::DerefMut;
+use std::collections::BinaryHeap;
+use std::ops::{DerefMut, Range};
+use std::panic::AssertUnwindSafe;
 use std::sync::{
     Arc,
-    atomic::{AtomicBool, AtomicUsize, Ordering},
+    atomic::{AtomicBool, Ordering},
+    Condvar,
     Mutex,
+    OnceLock,
 };
+use std::time::Duration;
+
+#[cfg(feature = "rayon")]
+use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
+
+#[cfg(all(feature = "sse2", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+use crate::simd;
+
+/// An error in the pattern scanner.<br>
+/// This encapsulates all possible errors that can occur when scanning for a pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanError {
+    /// Thrown when the callback panicked during the scan.
+    CallbackPanicked,
+    /// Thrown when the data is shorter than the pattern, so it can't contain any match.
+    DataTooSmall,
+    /// Thrown when the scan found more matches than the allowed maximum.
+    TooManyMatches {
+        /// The maximum number of matches.
+        cap: usize,
+    },
+}
+
+impl std::fmt::Display for ScanError {
+    /// Formats the various errors that can occur when scanning for a pattern.<br><br>
+    ///
+    /// # Arguments
+    /// * `f` - The formatter.
+    ///
+    /// # Returns
+    /// Whether the formatting was successful or not.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CallbackPanicked => write!(f, "the callback panicked during the scan"),
+            Self::DataTooSmall => write!(f, "the data is shorter than the pattern"),
+            Self::TooManyMatches { cap } => write!(f, "the scan found more than {} matches", cap),
+        }
+    }
+}
+
+impl std::error::Error for ScanError {}
 
 /// A pattern that can be used to scan for matches in a byte array.<br><br>
 ///
@@ -11,15 +56,98 @@ use std::sync::{
 /// using the [`PatternBuilder`](struct.PatternBuilder.html) struct.<br><br>
 ///
 /// Internally, a pattern is represented as a vector of bytes for the signature,
-/// a vector of booleans for the mask, and the number of threads to use.
+/// a vector of bit masks for the mask, and the number of threads to use.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Pattern {
     signature: Vec<u8>,
-    mask: Vec<bool>,
+    mask: Vec<u8>,
     threads: usize,
     start_offset: usize,
+    pub(crate) exclusions: Vec<(isize, u8)>,
+    pub(crate) checksums: Vec<(Range<usize>, u32)>,
+    pub(crate) overlapping: bool,
+    pub(crate) min_distance: usize,
+    pub(crate) fields: Vec<(String, usize)>,
+    pub(crate) auto_threads: bool,
+    optional: Vec<usize>,
+    runs: Vec<Run>,
+    mirror: (Vec<u8>, Vec<u8>),
+    anchor: Option<Anchor>,
+    secondary_anchor: Option<Anchor>,
+    fragment: Option<Fragment>,
+    rolling_hash: Option<u64>,
+    #[cfg(all(feature = "sse2", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+    short: Option<([u8; simd::SHORT_LEN], [u8; simd::SHORT_LEN])>,
+}
+
+/// Size of the data scanned to measure the throughput for [`Pattern::estimate_duration`].
+const CALIBRATION_SIZE: usize = 1024 * 1024;
+
+/// Amount of data for each thread of the patterns that choose the number of threads
+/// at scan time. (see `PatternBuilder::with_auto_threads`)
+const AUTO_THREADS_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Number of bytes before a match that are searched for the end of the previous
+/// instruction by [`Pattern::scan_with_instruction_hint`].
+const INSTRUCTION_HINT_WINDOW: usize = 32;
+
+/// Alignment of the instruction boundaries assumed by [`Pattern::scan_with_instruction_hint`]
+/// when no instruction end is found, as compilers usually align functions to 16 bytes.
+const INSTRUCTION_HINT_ALIGN: usize = 16;
+
+/// Minimum length of the signatures without wildcards that are searched with a rolling hash.
+const ROLLING_HASH_MIN_LEN: usize = 64;
+
+/// Multiplier of the polynomial rolling hash. (the hash is computed modulo 2^64)
+const ROLLING_HASH_BASE: u64 = 0x100000001B3;
+
+/// A byte of the signature that is checked before comparing the whole signature.<br>
+/// The anchor matches when `data[index] & bits == value`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Anchor {
+    index: usize,
+    value: u8,
+    bits: u8,
+}
+
+/// A run of consecutive bytes of the signature that are not wildcards.<br>
+/// The matcher only compares the runs, so the wildcard gaps between them are skipped
+/// regardless of their length.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Run {
+    range: Range<usize>,
+    /// Whether all the bytes of the run are fully fixed, so that they can be compared
+    /// without applying the mask.
+    exact: bool,
+}
+
+/// The longest run of fully fixed bytes of the signature, which is searched as a
+/// substring before comparing the whole signature.<br>
+/// The searcher is built once with the pattern, and reused by all the scans.
+#[derive(Debug, Clone)]
+struct Fragment {
+    range: Range<usize>,
+    finder: memchr::memmem::Finder<'static>,
+}
+
+impl PartialEq for Fragment {
+    // The searcher only depends on the bytes of the range, which are part of the signature.
+    fn eq(&self, other: &Self) -> bool {
+        self.range == other.range
+    }
 }
 
+impl Eq for Fragment {}
+
+/// Bytes that are frequent in executable code and data, from the most to the least frequent.<br>
+/// This is used to estimate the rarity of the fixed bytes when selecting the anchors,
+/// and every byte that is not in this list is considered rare.
+const COMMON_BYTES: [u8; 24] = [
+    0x00, 0xFF, 0xCC, 0x48, 0x8B, 0x89, 0x0F, 0x90,
+    0xE8, 0x24, 0x44, 0x4C, 0x01, 0x85, 0x83, 0xC3,
+    0x74, 0x8D, 0x45, 0x41, 0x20, 0x10, 0x08, 0xC0,
+];
+
 impl Pattern {
     /// Creates a new pattern from the given signature, mask and threads number.<br><br>
     ///
@@ -29,8 +157,35 @@ impl Pattern {
     /// * `threads` - The number of threads to use.
     ///
     /// # Returns
+    /// The newly created pattern.<br>
+    /// If the signature and the mask have different lengths, the longer one is truncated.
+    pub fn new(signature: Vec<u8>, mask: Vec<bool>, threads: usize) -> Self {
+        let mask = mask.into_iter()
+            .map(|fixed| if fixed { 0xFF } else { 0x00 })
+            .collect();
+
+        Self::from_bit_masks(signature, mask, threads)
+    }
+
+    /// Creates a new pattern from the given signature, bit masks and threads number.<br><br>
+    ///
+    /// Each byte of the mask contains the bits of the corresponding signature byte
+    /// that must match, so `0xFF` represents a fixed byte and `0x00` a wildcard byte.<br><br>
+    ///
+    /// # Arguments
+    /// * `signature` - The signature to scan for.
+    /// * `mask` - The bit masks of the signature bytes.
+    /// * `threads` - The number of threads to use.
+    ///
+    /// # Returns
     /// The newly created pattern.
-    pub fn new(mut signature: Vec<u8>, mut mask: Vec<bool>, threads: usize) -> Self {
+    pub(crate) fn from_bit_masks(mut signature: Vec<u8>, mut mask: Vec<u8>, threads: usize) -> Self {
+        // The signature and the mask are not validated by the public constructor, so
+        // only keep the bytes that have both a value and a mask.
+        let length = signature.len().min(mask.len());
+        signature.truncate(length);
+        mask.truncate(length);
+
         // Optimize the pattern by removing the trailing wildcards.
         //
         // Example:
@@ -40,13 +195,14 @@ impl Pattern {
         // This is done by calculating the actual offsets from the beginning and
         // end of the pattern, and then slicing the vectors to only keep the
         // relevant bytes.
-        let mut start_offset = mask.iter().take_while(|&&x| x == false).count();
-        let end_offset = mask.iter().rev().take_while(|&&x| x == false).count();
+        let mut start_offset = mask.iter().take_while(|&&x| x == 0).count();
+        let end_offset = mask.iter().rev().take_while(|&&x| x == 0).count();
 
         // Only resize the vectors if there is at least one non-wildcard byte.
         // I have no idea why anyone would want to scan for a pattern that is
         // entirely made of wildcards, but hey, it's their choice.
         if start_offset != mask.len() {
+            debug_assert!(start_offset + end_offset < mask.len());
             signature = signature[start_offset..signature.len() - end_offset].to_vec();
             mask = mask[start_offset..mask.len() - end_offset].to_vec();
         } else {
@@ -54,125 +210,2243 @@ impl Pattern {
             start_offset = 0;
         }
 
-        Self {
+        // Clear the bits that are not compared, so that the masked data bytes can
+        // be compared directly with the signature.
+        signature.iter_mut().zip(&mask).for_each(|(byte, &bits)| *byte &= bits);
+
+        // Group the bytes that are not wildcards in runs, so that the gaps between
+        // them are skipped in a single step when comparing the signature.
+        let mut runs: Vec<Run> = vec![];
+        for (index, &bits) in mask.iter().enumerate() {
+            if bits == 0 {
+                continue;
+            }
+
+            match runs.last_mut() {
+                Some(run) if run.range.end == index => {
+                    run.range.end += 1;
+                    run.exact &= bits == 0xFF;
+                }
+                _ => runs.push(Run { range: index..index + 1, exact: bits == 0xFF }),
+            }
+        }
+
+        // Short signatures are padded once, so that they can be compared with the
+        // data in a single SSE2 comparison.
+        #[cfg(all(feature = "sse2", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+        let short = (2..=simd::SHORT_LEN).contains(&signature.len()).then(|| {
+            let mut padded = ([0u8; simd::SHORT_LEN], [0u8; simd::SHORT_LEN]);
+            padded.0[..signature.len()].copy_from_slice(&signature);
+            padded.1[..mask.len()].copy_from_slice(&mask);
+            padded
+        });
+
+        // The signature and the mask are reversed once, as they are compared at every
+        // position of the bidirectional scans.
+        let mirror = (
+            signature.iter().rev().copied().collect(),
+            mask.iter().rev().copied().collect(),
+        );
+
+        let mut pattern = Self {
             signature,
             mask,
             threads,
             start_offset,
+            exclusions: vec![],
+            checksums: vec![],
+            overlapping: true,
+            min_distance: 0,
+            fields: vec![],
+            auto_threads: false,
+            optional: vec![],
+            runs,
+            mirror,
+            anchor: None,
+            secondary_anchor: None,
+            fragment: None,
+            rolling_hash: None,
+            #[cfg(all(feature = "sse2", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+            short,
+        };
+
+        pattern.select_anchors(true);
+
+        // Long signatures without wildcards are searched with a rolling hash, as
+        // comparing the hash of each window is cheaper than comparing the anchors
+        // and the whole signature. Signatures with wildcards use the anchors.
+        if pattern.signature.len() >= ROLLING_HASH_MIN_LEN
+            && pattern.mask.iter().all(|&bits| bits == 0xFF) {
+            pattern.rolling_hash = Some(Self::hash_window(&pattern.signature));
+        }
+
+        pattern
+    }
+
+    /// Internal function that computes the polynomial hash of a window of data.<br><br>
+    ///
+    /// # Arguments
+    /// * `window` - The bytes of the window.
+    ///
+    /// # Returns
+    /// The hash of the window.
+    fn hash_window(window: &[u8]) -> u64 {
+        window.iter().fold(0u64, |hash, &byte| {
+            hash.wrapping_mul(ROLLING_HASH_BASE).wrapping_add(byte as u64)
+        })
+    }
+
+    /// Selects the bytes of the signature that are checked before comparing
+    /// the whole signature with the data.<br><br>
+    ///
+    /// With dual anchors, the two rarest fixed bytes are selected, so that sparse
+    /// patterns only require a full comparison where both of them match.<br>
+    /// The longest run of at least two fixed bytes is also selected, as searching for
+    /// a longer sequence skips more positions than searching for a single byte.<br>
+    /// Otherwise, the first byte of the signature is the only anchor. This byte is
+    /// always not masked due to the optimizations in the constructor.<br>
+    /// The bytes after the first optional byte are never selected, as their position
+    /// in the matches is not fixed.<br><br>
+    ///
+    /// # Arguments
+    /// * `dual` - Whether to select two anchors based on their rarity.
+    pub(crate) fn select_anchors(&mut self, dual: bool) {
+        let fixed_len = self.optional.first().copied().unwrap_or(self.mask.len());
+        let anchor_at = |index: usize| Anchor {
+            index,
+            value: self.signature[index],
+            bits: self.mask[index],
+        };
+
+        if !dual {
+            self.anchor = self.mask[..fixed_len].first().filter(|&&bits| bits != 0).map(|_| anchor_at(0));
+            self.secondary_anchor = None;
+            self.fragment = None;
+            return;
+        }
+
+        // Find the longest run of fixed bytes, preferring the first one if there are many.
+        let mut fragment: Option<Range<usize>> = None;
+        let mut start = 0;
+        for index in 0..=fixed_len {
+            if index < fixed_len && self.mask[index] == 0xFF {
+                continue;
+            }
+            if index - start >= 2 && fragment.as_ref().is_none_or(|longest| index - start > longest.len()) {
+                fragment = Some(start..index);
+            }
+            start = index + 1;
+        }
+        self.fragment = fragment.map(|range| Fragment {
+            finder: memchr::memmem::Finder::new(&self.signature[range.clone()]).into_owned(),
+            range,
+        });
+
+        // Sort the fixed bytes by how common they are, preferring the fully fixed
+        // ones, as they can be searched with `memchr`.
+        let mut candidates = (0..fixed_len)
+            .filter(|&index| self.mask[index] != 0)
+            .collect::<Vec<_>>();
+        candidates.sort_by_key(|&index| {
+            let commonness = COMMON_BYTES.iter()
+                .position(|&byte| byte == self.signature[index])
+                .map_or(0, |rank| COMMON_BYTES.len() - rank);
+            (self.mask[index] != 0xFF, commonness, index)
+        });
+
+        let mut anchors = candidates.into_iter().map(anchor_at);
+        self.anchor = anchors.next();
+        self.secondary_anchor = anchors.next();
+    }
+
+    /// Marks bytes of the signature as optional, so that the matches may either
+    /// contain or omit each of them.<br><br>
+    ///
+    /// # Arguments
+    /// * `indices` - The indices of the optional bytes in the signature, including
+    ///   the leading wildcards.
+    pub(crate) fn set_optional(&mut self, indices: &[usize]) {
+        self.optional = indices.iter()
+            .filter_map(|&index| index.checked_sub(self.start_offset))
+            .filter(|&index| index < self.signature.len())
+            .collect();
+        self.optional.sort_unstable();
+        self.optional.dedup();
+
+        if !self.optional.is_empty() {
+            // The windows of the data that match have different lengths.
+            self.rolling_hash = None;
+            self.select_anchors(true);
+        }
+    }
+
+    /// # Returns
+    /// The number of threads to use in scans of this pattern.<br>
+    /// If the number of threads is chosen at scan time, this is the maximum number of threads.
+    pub fn get_threads(&self) -> usize {
+        self.threads
+    }
+
+    /// Internal function that computes the number of threads to use to scan the data.<br><br>
+    ///
+    /// # Arguments
+    /// * `data_len` - The length of the data to scan.
+    ///
+    /// # Returns
+    /// The number of threads of the pattern, or if they are chosen at scan time, a thread
+    /// for each `AUTO_THREADS_CHUNK_SIZE` bytes of data, up to the number of threads of the pattern.
+    pub(crate) fn threads_for(&self, data_len: usize) -> usize {
+        if self.auto_threads {
+            (data_len / AUTO_THREADS_CHUNK_SIZE).clamp(1, self.threads.max(1))
+        } else {
+            self.threads
+        }
+    }
+
+    /// Creates a key that identifies what the pattern matches, regardless of how
+    /// it is scanned.<br><br>
+    ///
+    /// The equality of patterns also compares their thread count, so two patterns
+    /// that find the same matches with a different number of threads are different.<br>
+    /// The key only contains the settings that affect which matches are reported,
+    /// (the signature, the mask and the matching options) so it can be used to
+    /// cache the results of the scans.<br><br>
+    ///
+    /// # Returns
+    /// The match key of the pattern.
+    pub fn match_key(&self) -> MatchKey {
+        MatchKey {
+            signature: self.signature.clone(),
+            mask: self.mask.clone(),
+            start_offset: self.start_offset,
+            exclusions: self.exclusions.clone(),
+            checksums: self.checksums.clone(),
+            optional: self.optional.clone(),
+            overlapping: self.overlapping,
+            min_distance: self.min_distance,
+        }
+    }
+
+    /// # Returns
+    /// The number of bytes covered by a match of this pattern.<br>
+    /// This includes the leading wildcards, but not the trailing ones, which
+    /// are ignored during the scan.<br>
+    /// If the pattern has optional bytes, this is the length of the matches that contain all of them.
+    pub fn len(&self) -> usize {
+        self.start_offset + self.signature.len()
+    }
+
+    /// # Returns
+    /// The number of bytes covered by the shortest possible match of this pattern,
+    /// which omits all the optional bytes.
+    pub fn min_len(&self) -> usize {
+        self.len() - self.optional.len()
+    }
+
+    /// # Returns
+    /// True if the pattern does not contain any bytes, otherwise false.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks whether data of the given length is long enough to contain a match.<br>
+    /// This can be used to report an error before scanning, instead of silently
+    /// finding no matches.<br><br>
+    ///
+    /// # Arguments
+    /// * `data_len` - The length of the data to scan.
+    ///
+    /// # Returns
+    /// True if the data is at least as long as the pattern, otherwise false.
+    pub fn fits(&self, data_len: usize) -> bool {
+        data_len >= self.len()
+    }
+
+    /// Formats the pattern as an IDA-style string. (e.g. `48 8B ? ? 90`)<br><br>
+    ///
+    /// The leading wildcards are preserved, while the trailing ones are not, as
+    /// they are removed when the pattern is created.<br>
+    /// Bytes that are only partially fixed can't be represented in this style,
+    /// so they are formatted as wildcards, while optional bytes are enclosed
+    /// in parentheses. (e.g. `(48)`)<br><br>
+    ///
+    /// # Returns
+    /// The IDA-style representation of the pattern.
+    pub fn to_ida_style(&self) -> String {
+        let leading = std::iter::repeat_n("?".to_string(), self.start_offset);
+        let bytes = self.signature.iter().zip(&self.mask).enumerate().map(|(index, (byte, &bits))| {
+            if self.is_optional(index) {
+                format!("({:02X})", byte)
+            } else if bits == 0xFF {
+                format!("{:02X}", byte)
+            } else {
+                "?".to_string()
+            }
+        });
+
+        leading.chain(bytes).collect::<Vec<_>>().join(" ")
+    }
+
+    /// Formats the pattern as a string of non-spaced hex bytes. (e.g. `488b????90`)<br><br>
+    ///
+    /// The leading wildcards are preserved, while the trailing ones are not, as
+    /// they are removed when the pattern is created.<br>
+    /// Bytes that are only partially fixed can't be represented in this style,
+    /// so they are formatted as wildcards.<br><br>
+    ///
+    /// # Returns
+    /// The hexadecimal string representation of the pattern.
+    pub fn to_hex_string(&self) -> String {
+        let leading = std::iter::repeat_n("??".to_string(), self.start_offset);
+        let bytes = self.signature.iter().zip(&self.mask).map(|(byte, &bits)| {
+            if bits == 0xFF {
+                format!("{:02x}", byte)
+            } else {
+                "??".to_string()
+            }
+        });
+
+        leading.chain(bytes).collect()
+    }
+
+    /// Formats the pattern as a code-style signature and mask.
+    /// (e.g. `\x48\x8B\x00\x00\x90` and `..??.`)<br><br>
+    ///
+    /// The wildcard bytes are represented by `\x00` in the signature, and by `?`
+    /// in the mask.<br>
+    /// The leading wildcards are preserved, while the trailing ones are not, as
+    /// they are removed when the pattern is created.<br>
+    /// Bytes that are only partially fixed can't be represented in this style,
+    /// so they are formatted as wildcards.<br><br>
+    ///
+    /// # Returns
+    /// The escaped signature and the mask of the pattern.
+    pub fn to_code_style(&self) -> (String, String) {
+        let leading = std::iter::repeat_n((0, false), self.start_offset);
+        let bytes = self.signature.iter().copied().zip(self.mask.iter().map(|&bits| bits == 0xFF));
+
+        leading.chain(bytes)
+            .map(|(byte, fixed)| {
+                if fixed {
+                    (format!("\\x{:02X}", byte), '.')
+                } else {
+                    ("\\x00".to_string(), '?')
+                }
+            })
+            .unzip()
+    }
+
+    /// Splits the pattern at its wildcards into the runs of fixed bytes.<br><br>
+    ///
+    /// This is useful to search the pattern in multiple stages, such as by looking
+    /// for its longest fragment first.<br>
+    /// Bytes that are only partially fixed and optional bytes are treated as wildcards,
+    /// and the offsets are those of the matches that contain all the optional bytes.<br><br>
+    ///
+    /// # Returns
+    /// The maximal runs of fixed bytes, with their offsets in the pattern, including
+    /// the leading wildcards, in ascending order.
+    ///
+    /// # Example
+    /// ```
+    /// let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? ? 90 90")
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// assert_eq!(
+    ///     pattern.fixed_fragments(),
+    ///     vec![(0, vec![0x48, 0x8B]), (4, vec![0x90, 0x90])],
+    /// );
+    /// ```
+    pub fn fixed_fragments(&self) -> Vec<(usize, Vec<u8>)> {
+        let mut fragments: Vec<(usize, Vec<u8>)> = vec![];
+        let mut previous = None;
+
+        for (index, (&byte, &bits)) in self.signature.iter().zip(&self.mask).enumerate() {
+            if bits != 0xFF || self.is_optional(index) {
+                continue;
+            }
+
+            match fragments.last_mut() {
+                Some((_, bytes)) if previous == Some(index - 1) => bytes.push(byte),
+                _ => fragments.push((self.start_offset + index, vec![byte])),
+            }
+            previous = Some(index);
+        }
+
+        fragments
+    }
+
+    /// Performs the AOB scan in the given slice.<br><br>
+    ///
+    /// If specified, this function will split the data into chunks and scan
+    /// each chunk in parallel.<br><br>
+    ///
+    /// The data can be anything that can be borrowed as a byte slice, such as
+    /// `Vec<u8>`, `Cow<[u8]>` or a byte array, and is never copied.<br><br>
+    ///
+    /// With the `tracing` feature, the scan is recorded in a span with the pattern,
+    /// the data length, the thread count and the number of matches, and each match
+    /// is recorded as a `trace` event.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match as an argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan(
+        &self,
+        data: impl AsRef<[u8]>,
+        callback: impl FnMut(usize) -> bool + Send + Sync,
+    ) -> bool {
+        self.scan_stoppable(data.as_ref(), &AtomicBool::new(false), callback)
+    }
+
+    /// Internal function that performs the AOB scan in the given slice, until the
+    /// callback stops it or the given flag is set. (see [`scan`](#method.scan))<br><br>
+    ///
+    /// This allows the scans of multiple slices to be stopped together, by sharing the flag.<br>
+    /// The flag is set when the callback stops the scan.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `finished` - The flag that stops the scan when set.
+    /// * `callback` - The callback to execute when a match is found.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub(crate) fn scan_stoppable(
+        &self,
+        data: &[u8],
+        finished: &AtomicBool,
+        mut callback: impl FnMut(usize) -> bool + Send + Sync,
+    ) -> bool {
+        #[cfg(feature = "tracing")]
+        let span = tracing::info_span!(
+            "scan",
+            pattern = %self.to_ida_style(),
+            data_len = data.len(),
+            threads = self.threads_for(data.len()),
+            matches = tracing::field::Empty,
+        );
+        #[cfg(feature = "tracing")]
+        let _entered = span.enter();
+        #[cfg(feature = "tracing")]
+        let mut matches = 0usize;
+
+        let found = self.scan_until(data, finished, |offset, control| {
+            // The callback may run in the worker threads, which are not inside the span.
+            #[cfg(feature = "tracing")]
+            {
+                matches += 1;
+                tracing::trace!(parent: &span, offset, "match");
+            }
+
+            if !callback(offset) {
+                // Non-overlapping multi-threaded scans run the callback after the
+                // threads are joined, so the flag is not set by the scan itself.
+                finished.store(true, Ordering::Relaxed);
+                control.stop();
+            }
+        });
+
+        #[cfg(feature = "tracing")]
+        span.record("matches", matches);
+        found
+    }
+
+    /// Performs the AOB scan in the given slice, passing to the callback the index
+    /// of the worker thread that found each match.<br><br>
+    ///
+    /// This is useful to debug multi-threaded scans, or to keep a state for each thread.<br>
+    /// The threads are numbered from `0` in the order of their chunks of data, and
+    /// single-threaded scans always report `0`.<br>
+    /// The index is the one of the thread that runs the callback, so the non-overlapping
+    /// multi-threaded scans, whose matches are reported by the calling thread after the
+    /// workers are joined, also report `0`.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match and the index of the thread as arguments.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_with_thread_id(
+        &self,
+        data: &[u8],
+        callback: impl Fn(usize, usize) -> bool + Send + Sync,
+    ) -> bool {
+        self.scan_control(data, |offset, control| {
+            if !callback(offset, control.thread) {
+                control.stop();
+            }
+        })
+    }
+
+    /// Performs the AOB scan in multiple independent buffers, distributing them
+    /// across the rayon thread pool.<br><br>
+    ///
+    /// Each buffer is scanned by a single worker of the pool, regardless of the number
+    /// of threads of the pattern, and the matches never span two buffers.<br>
+    /// The matches are reported in no particular order.<br><br>
+    ///
+    /// # Arguments
+    /// * `buffers` - The data slices to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the index of the buffer and the offset of the match in it as arguments.
+    ///    - It should return `true` to continue scanning, or `false` to stop the scan of all the buffers.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    #[cfg(feature = "rayon")]
+    pub fn scan_many<'a, I>(
+        &self,
+        buffers: I,
+        callback: impl FnMut(usize, usize) -> bool + Send + Sync,
+    ) -> bool
+    where
+        I: IntoParallelIterator<Item = &'a [u8]>,
+        I::Iter: IndexedParallelIterator,
+    {
+        let finished = AtomicBool::new(false);
+        let callback = Mutex::new(callback);
+
+        buffers.into_par_iter()
+            .enumerate()
+            .map(|(index, data)| {
+                self.scan_chunk(
+                    data,
+                    (0, data.len()),
+                    0,
+                    &finished,
+                    &Mutex::new(|offset, control: &mut ScanControl| {
+                        // The callback is shared by all the workers of the pool.
+                        let mut callback = match callback.lock() {
+                            Ok(callback) => callback,
+                            // The callback panicked in another worker, and the panic is
+                            // propagated by rayon, so stop scanning.
+                            Err(_) => return control.stop(),
+                        };
+
+                        // Check the flag again, as the scan may have been stopped
+                        // while waiting for the lock.
+                        if finished.load(Ordering::Relaxed) || !callback.deref_mut()(index, offset) {
+                            control.stop();
+                        }
+                    }),
+                )
+            })
+            .reduce(|| false, |a, b| a || b)
+    }
+
+    /// Performs the AOB scan in the given slice, reporting the matches as virtual
+    /// addresses relative to the given image base.<br><br>
+    ///
+    /// This is useful when the data is a dump of a module loaded at a known address.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `image_base` - The address at which the data is loaded.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the address of the match (`image_base + offset`) as an argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_based(
+        &self,
+        data: &[u8],
+        image_base: u64,
+        mut callback: impl FnMut(u64) -> bool + Send + Sync,
+    ) -> bool {
+        self.scan(data, move |offset| callback(image_base.wrapping_add(offset as u64)))
+    }
+
+    /// Performs the AOB scan in the given slice, reporting the matches as distances
+    /// from the end of the data.<br><br>
+    ///
+    /// This is useful for formats whose structures are located from the end, such as
+    /// trailers and footers.<br>
+    /// The distance is measured from the end of the match, so a match that ends with the
+    /// data is reported as `0`, and the match starts `distance + pattern.len()` bytes
+    /// before the end of the data.<br>
+    /// The matches that omit optional bytes are shorter than the pattern, so their
+    /// distance is measured from their actual end.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the number of bytes after the end of the match as an argument.
+    ///      (`data.len() - offset - pattern.len()` if it contains all the optional bytes)
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    ///
+    /// # Example
+    /// ```
+    /// let pattern = aobscan::PatternBuilder::from_ida_style("50 4B 05 06")
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// let mut distances = vec![];
+    /// pattern.scan_from_end(b"\x00\x50\x4B\x05\x06\x00\x00", |distance| {
+    ///     distances.push(distance);
+    ///     true
+    /// });
+    /// assert_eq!(distances, vec![2]);
+    /// ```
+    pub fn scan_from_end(
+        &self,
+        data: &[u8],
+        mut callback: impl FnMut(usize) -> bool + Send + Sync,
+    ) -> bool {
+        self.scan(data, move |offset| callback(data.len() - offset - self.match_len(data, offset)))
+    }
+
+    /// Performs the AOB scan in the given slice, passing to the callback the ordinal
+    /// of each match.<br><br>
+    ///
+    /// The ordinals start from `0` and are assigned in the order in which the matches
+    /// are found, which is not the order of their offsets in multi-threaded scans.<br>
+    /// The callback is never run by two threads at once, so each match always receives
+    /// a unique ordinal, and the ordinals of a complete scan are `0..count`.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the ordinal and the offset of the match as arguments.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_enumerated(
+        &self,
+        data: &[u8],
+        mut callback: impl FnMut(usize, usize) -> bool + Send + Sync,
+    ) -> bool {
+        let mut ordinal = 0;
+        self.scan(data, move |offset| {
+            ordinal += 1;
+            callback(ordinal - 1, offset)
+        })
+    }
+
+    /// Performs the AOB scan in the given slice, passing to the callback a guess of
+    /// where the instruction containing each match starts.<br><br>
+    ///
+    /// This is a heuristic, not a disassembler: the bytes before the match are searched
+    /// backwards for a `C3` (`ret`) or `CC` (`int3`) byte, which usually ends the previous
+    /// function, and the hint is the byte after it.<br>
+    /// If there are no such bytes within the 32 bytes before the match, the hint is the
+    /// closest 16-byte aligned offset at or before the match, where functions usually start.<br>
+    /// The hint is therefore often wrong in the middle of a function, or when the
+    /// searched bytes are part of an operand.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match and the likely start of its
+    ///      instruction, which is never after the match, as arguments.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_with_instruction_hint(
+        &self,
+        data: &[u8],
+        mut callback: impl FnMut(usize, usize) -> bool + Send + Sync,
+    ) -> bool {
+        self.scan(data, |offset| {
+            let window_start = offset.saturating_sub(INSTRUCTION_HINT_WINDOW);
+            let hint = data[window_start..offset]
+                .iter()
+                .rposition(|&byte| byte == 0xC3 || byte == 0xCC)
+                .map_or(offset - offset % INSTRUCTION_HINT_ALIGN, |index| window_start + index + 1);
+
+            callback(offset, hint)
+        })
+    }
+
+    /// Performs the AOB scan in the given slice, looking for both the pattern and
+    /// its mirror, in which the order of the bytes is reversed.<br><br>
+    ///
+    /// This is useful for data that stores the same sequences in both orders.<br>
+    /// Both orientations are checked at each position in a single pass, which is always
+    /// single-threaded. The mirror covers the same bytes as the signature without the
+    /// leading wildcards, so `? 48 8B` is mirrored as `8B 48`.<br>
+    /// The additional constraints of the pattern (such as the exclusions) are only checked
+    /// for the matches of the pattern, and the optional bytes are always part of the mirror.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match and whether it is a match of the mirror as arguments.
+    ///    - If both orientations match at the same offset, the pattern is reported first.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_bidirectional(
+        &self,
+        data: &[u8],
+        mut callback: impl FnMut(usize, bool) -> bool,
+    ) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+
+        let (mirror_signature, mirror_mask) = &self.mirror;
+
+        let mut found = false;
+        for offset in 0..data.len() {
+            let forward = self.matches_at(data, offset);
+            let reversed = data.get(offset..offset + mirror_signature.len())
+                .is_some_and(|window| {
+                    window.iter()
+                        .zip(mirror_signature.iter().zip(mirror_mask))
+                        .all(|(&byte, (&sig, &bits))| byte & bits == sig)
+                });
+
+            for (matched, is_reversed) in [(forward, false), (reversed, true)] {
+                if matched {
+                    found = true;
+                    if !callback(offset, is_reversed) {
+                        return found;
+                    }
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Performs a tolerant scan in the given slice, which also reports the positions
+    /// where some of the fixed bytes of the pattern do not match.<br><br>
+    ///
+    /// A position matches if at most `max_mismatches` of its fixed bytes differ from
+    /// the signature. Wildcards never count as mismatches.<br>
+    /// The exclusions of the pattern are still applied to all the matches.<br><br>
+    ///
+    /// This scan can't skip the positions where the first byte differs, so it compares
+    /// the whole signature at every position of the data, and it is always single-threaded.<br>
+    /// It is therefore much slower than [`scan`](#method.scan), and should only be
+    /// used when the tolerance is needed.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `max_mismatches` - The maximum number of fixed bytes that may differ.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match and its number of mismatched bytes as arguments.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_fuzzy(
+        &self,
+        data: &[u8],
+        max_mismatches: usize,
+        mut callback: impl FnMut(usize, usize) -> bool,
+    ) -> bool {
+        let last = match data.len().checked_sub(self.len()) {
+            Some(last) => last,
+            None => return false,
+        };
+
+        let mut found = false;
+        for offset in 0..=last {
+            let window = &data[offset + self.start_offset..offset + self.len()];
+
+            // Count the mismatched fixed bytes, giving up as soon as there are too many.
+            let mut mismatches = 0;
+            for ((&byte, &sig), &bits) in window.iter().zip(&self.signature).zip(&self.mask) {
+                if byte & bits != sig {
+                    mismatches += 1;
+                    if mismatches > max_mismatches {
+                        break;
+                    }
+                }
+            }
+
+            if mismatches > max_mismatches || !self.check_constraints(data, offset) {
+                continue;
+            }
+
+            found = true;
+            if !callback(offset, mismatches) {
+                break;
+            }
+        }
+
+        found
+    }
+
+    /// Performs a tolerant scan in the given slice, which also reports the positions
+    /// where a single byte was inserted in or deleted from a match of the pattern.<br><br>
+    ///
+    /// At each position, the data is compared with the signature as it is, with an
+    /// additional byte between two bytes of the signature, and without one of the bytes
+    /// of the signature, other than the first and the last one. (an edit at the edges
+    /// is just a shorter match of the rest of the pattern)<br>
+    /// The additional constraints of the pattern (such as the exclusions) are only checked
+    /// for the exact matches, and the optional bytes are treated as fixed bytes.<br><br>
+    ///
+    /// The longest matching prefix and suffix of each alignment are measured, so the
+    /// scan costs up to three comparisons of the signature at each position where the
+    /// first byte matches, in `O(data.len() * pattern.len())` time. It is always
+    /// single-threaded, and much slower than [`scan`](#method.scan) on repetitive data.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match and the difference between its length
+    ///      and the length of the pattern (`0` if exact, `1` if inserted, `-1` if deleted) as arguments.
+    ///    - If more than one alignment matches at the same offset, the match is only reported
+    ///      once, with the first of them in this order.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_indel(
+        &self,
+        data: &[u8],
+        mut callback: impl FnMut(usize, isize) -> bool,
+    ) -> bool {
+        let sig_len = self.signature.len();
+        if sig_len == 0 {
+            return false;
+        }
+
+        let mut found = false;
+        for start in self.start_offset..data.len() {
+            let offset = start - self.start_offset;
+            let window = &data[start..];
+
+            // The number of leading bytes of the signature matched without edits.
+            let prefix = window
+                .iter()
+                .zip(&self.signature)
+                .zip(&self.mask)
+                .take_while(|((&byte, &sig), &bits)| byte & bits == sig)
+                .count();
+
+            // The number of trailing bytes of the signature matched by a window of the given length.
+            let suffix = |length: usize| {
+                window[..length]
+                    .iter()
+                    .rev()
+                    .zip(self.signature.iter().rev())
+                    .zip(self.mask.iter().rev())
+                    .take_while(|((&byte, &sig), &bits)| byte & bits == sig)
+                    .count()
+            };
+
+            // An edit after the byte `k` needs the prefix to reach `k`, and the suffix to cover
+            // the rest of the signature, with `k` between the first and the last byte.
+            let difference = if prefix == sig_len && self.check_constraints(data, offset) {
+                0
+            } else if prefix > 0
+                && sig_len >= 2
+                && window.len() > sig_len
+                && sig_len.saturating_sub(suffix(sig_len + 1)).max(1) <= prefix.min(sig_len - 1)
+            {
+                1
+            } else if prefix > 0
+                && sig_len >= 3
+                && window.len() >= sig_len - 1
+                && (sig_len - 1).saturating_sub(suffix(sig_len - 1)).max(1) <= prefix.min(sig_len - 2)
+            {
+                -1
+            } else {
+                continue;
+            };
+
+            found = true;
+            if !callback(offset, difference) {
+                break;
+            }
+        }
+
+        found
+    }
+
+    /// Performs the AOB scan in the given slice, catching the panics of the callback.<br><br>
+    ///
+    /// This works like [`scan`](#method.scan), but if the callback panics, the scan
+    /// is stopped in all the threads and an error is returned, instead of propagating
+    /// the panic to the caller.<br>
+    /// After a panic, the callback is never called again.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match as an argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// Ok(true) if at least one match was found, Ok(false) if no matches were found,
+    /// Err if the callback panicked.
+    ///
+    /// # Errors
+    /// * `ScanError::DataTooSmall` - The data is shorter than the pattern. (see [`fits`](#method.fits))
+    /// * `ScanError::CallbackPanicked` - The callback panicked.
+    pub fn try_scan(
+        &self,
+        data: &[u8],
+        mut callback: impl FnMut(usize) -> bool + Send + Sync,
+    ) -> Result<bool, ScanError> {
+        if !self.fits(data.len()) {
+            return Err(ScanError::DataTooSmall);
+        }
+
+        let mut panicked = false;
+
+        let found = self.scan_control(data, |offset, control| {
+            // Other threads might still be waiting for the callback after a panic.
+            if panicked {
+                control.stop();
+                return;
+            }
+
+            // The panic is caught while the callback mutex is still locked,
+            // so the mutex is never poisoned.
+            match std::panic::catch_unwind(AssertUnwindSafe(|| callback(offset))) {
+                Ok(true) => {}
+                Ok(false) => control.stop(),
+                Err(_) => {
+                    panicked = true;
+                    control.stop();
+                }
+            }
+        });
+
+        if panicked {
+            Err(ScanError::CallbackPanicked)
+        } else {
+            Ok(found)
+        }
+    }
+
+    /// Performs the AOB scan in the given slice, giving the callback control
+    /// over the flow of the scan.<br><br>
+    ///
+    /// This works like [`scan`](#method.scan), but instead of returning whether the
+    /// scan should continue, the callback receives a [`ScanControl`](struct.ScanControl.html)
+    /// that it can use to stop the scan or to skip ahead after a match.<br><br>
+    ///
+    /// In multi-threaded scans, skipping ahead only affects the chunk of data scanned
+    /// by the thread that found the match, and it never crosses into the next chunk.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match and the scan control as arguments.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_control(
+        &self,
+        data: &[u8],
+        callback: impl FnMut(usize, &mut ScanControl) + Send + Sync,
+    ) -> bool {
+        // Atomic flag to stop all threads if a match is found and accepted.
+        // The thread scope allows the threads to borrow the flag and the callback,
+        // so there is no need to allocate them on the heap.
+        let finished = AtomicBool::new(false);
+        self.scan_until(data, &finished, callback)
+    }
+
+    /// Performs the AOB scan in the given slice, stopping the workers once the
+    /// given time budget elapses.<br><br>
+    ///
+    /// This is useful to yield the CPU in services that run multiple scans, and
+    /// accept partial results for the scans that take too long.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `budget` - The maximum duration of the scan.
+    ///
+    /// # Returns
+    /// The offsets of the matches found within the budget, sorted in ascending order,
+    /// and whether the scan completed.<br>
+    /// A scan that ends at the same time as the budget may be reported as not completed.
+    pub fn scan_budgeted(&self, data: &[u8], budget: Duration) -> (Vec<usize>, bool) {
+        let finished = AtomicBool::new(false);
+        // Set by the scan when it ends, to wake up the watchdog before the budget elapses.
+        let done = (Mutex::new(false), Condvar::new());
+        let mut offsets = vec![];
+
+        let expired = std::thread::scope(|scope| {
+            // The watchdog stops the workers once the budget elapses.
+            let watchdog = scope.spawn(|| {
+                let (lock, condvar) = &done;
+                let guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+                let (_guard, timeout) = condvar
+                    .wait_timeout_while(guard, budget, |done| !*done)
+                    .unwrap_or_else(|e| e.into_inner());
+
+                if timeout.timed_out() {
+                    finished.store(true, Ordering::Relaxed);
+                }
+                timeout.timed_out()
+            });
+
+            self.scan_until(data, &finished, |offset, _| offsets.push(offset));
+
+            let (lock, condvar) = &done;
+            *lock.lock().unwrap_or_else(|e| e.into_inner()) = true;
+            condvar.notify_one();
+
+            watchdog.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+        });
+
+        // Multi-threaded scans report the matches in no particular order.
+        offsets.sort_unstable();
+        (offsets, !expired)
+    }
+
+    /// Performs the AOB scan in the given slice, calling a function at regular
+    /// intervals to give way to other tasks.<br><br>
+    ///
+    /// This is useful to scan large buffers in single-threaded runtimes, where a long
+    /// scan would block all the other tasks. The yield function can be, for example,
+    /// `std::thread::yield_now` or a helper that drives the runtime.<br>
+    /// The scan always runs in the current thread, regardless of the thread count of
+    /// the pattern.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `yield_every` - The number of bytes to scan between the calls of `yield_fn`.
+    ///    - If `0`, `yield_fn` is never called.
+    /// * `yield_fn` - The function called after every `yield_every` bytes are scanned.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match as an argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_yielding(
+        &self,
+        data: &[u8],
+        yield_every: usize,
+        mut yield_fn: impl FnMut(),
+        mut callback: impl FnMut(usize) -> bool + Send + Sync,
+    ) -> bool {
+        // The windows are scanned separately, so the matching options that depend
+        // on the previous matches are applied here, across all the windows.
+        let collector = Self {
+            overlapping: true,
+            min_distance: 0,
+            ..self.clone()
+        };
+
+        let window_size = if yield_every == 0 { data.len() } else { yield_every };
+        // Extend each window so that the matches starting in its last positions can
+        // be compared entirely, like the chunks of multi-threaded scans.
+        let overlap = self.signature.len().saturating_sub(1);
+        let finished = AtomicBool::new(false);
+
+        let mut found = false;
+        let mut next = 0;
+        let mut start = 0;
+        while start < data.len() {
+            let end = start.saturating_add(window_size).min(data.len());
+            collector.scan_chunk(
+                data,
+                (start, end.saturating_add(overlap).min(data.len())),
+                0,
+                &finished,
+                &Mutex::new(|offset: usize, control: &mut ScanControl| {
+                    if offset < next {
+                        return;
+                    }
+
+                    found = true;
+                    if !callback(offset) {
+                        control.stop();
+                    }
+
+                    let span = if self.overlapping { 1 } else { self.len() };
+                    next = offset.saturating_add(span).max(offset.saturating_add(self.min_distance));
+                }),
+            );
+
+            if finished.load(Ordering::Relaxed) {
+                break;
+            }
+
+            if yield_every != 0 && end - start == yield_every {
+                yield_fn();
+            }
+            start = end;
+        }
+
+        found
+    }
+
+    /// Performs the AOB scan in the given slice, reporting the matches in batches.<br><br>
+    ///
+    /// In multi-threaded scans, each thread collects its matches locally, and only
+    /// acquires the lock of the callback once per batch. This greatly reduces the
+    /// contention between the threads when the data contains many matches.<br>
+    /// Each batch contains the matches of a single thread in ascending order, but the
+    /// batches of different threads are reported in no particular order.<br>
+    /// The last batch of each thread can be shorter than `batch`.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `batch` - The maximum number of matches in a batch. (`0` is treated as `1`)
+    /// * `callback` - The callback to execute when a batch of matches is ready.
+    ///    - The callback receives the offsets of the matches in the batch as an argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_batched(
+        &self,
+        data: &[u8],
+        batch: usize,
+        mut callback: impl FnMut(&[usize]) -> bool + Send + Sync,
+    ) -> bool {
+        let batch = batch.max(1);
+
+        // The matches of single-threaded and reconciled scans are all reported by
+        // the calling thread, so there is no contention to avoid.
+        let threads = self.threads_for(data.len());
+        if threads <= 1 || !self.overlapping {
+            let mut buffer = Vec::with_capacity(batch);
+            let mut stopped = false;
+            let found = self.scan_control(data, |offset, control| {
+                buffer.push(offset);
+                if buffer.len() == batch {
+                    stopped = !callback(&buffer);
+                    buffer.clear();
+                    if stopped {
+                        control.stop();
+                    }
+                }
+            });
+
+            if !stopped && !buffer.is_empty() {
+                callback(&buffer);
+            }
+            return found;
+        }
+
+        let callback = Mutex::new(callback);
+        let finished = AtomicBool::new(false);
+
+        // Reports a batch of matches, and clears it.
+        // Returns false if the scan should stop.
+        let flush = |buffer: &mut Vec<usize>| {
+            let mut callback = match callback.lock() {
+                Ok(callback) => callback,
+                // The callback panicked in another thread, and the panic is
+                // propagated when the threads are joined.
+                Err(_) => {
+                    finished.store(true, Ordering::Relaxed);
+                    return false;
+                }
+            };
+
+            // Another thread may have stopped the scan while this one was waiting.
+            if finished.load(Ordering::Relaxed) {
+                return false;
+            }
+
+            // Stop the other threads if the callback panics in this one.
+            let _guard = FinishOnPanic(&finished);
+            let proceed = callback.deref_mut()(buffer);
+            buffer.clear();
+            if !proceed {
+                finished.store(true, Ordering::Relaxed);
+            }
+            proceed
+        };
+
+        std::thread::scope(|scope| {
+            let workers = (0..threads)
+                .map(|tc| {
+                    let range = Self::get_chunk_range(
+                        data.len(),
+                        threads,
+                        self.signature.len().saturating_sub(1),
+                        tc,
+                    );
+
+                    let (finished, flush) = (&finished, &flush);
+                    scope.spawn(move || {
+                        // The local callback is only used by this thread, so its lock
+                        // is never contended.
+                        let mut buffer = Vec::with_capacity(batch);
+                        let found = self.scan_chunk(
+                            data,
+                            range,
+                            tc,
+                            finished,
+                            &Mutex::new(|offset, control: &mut ScanControl| {
+                                buffer.push(offset);
+                                if buffer.len() == batch && !flush(&mut buffer) {
+                                    control.stop();
+                                }
+                            }),
+                        );
+
+                        if !buffer.is_empty() && !finished.load(Ordering::Relaxed) {
+                            flush(&mut buffer);
+                        }
+                        found
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            // Join all the workers, propagating the panics of the callback.
+            workers.into_iter().fold(false, |found, worker| {
+                worker.join().unwrap_or_else(|panic| std::panic::resume_unwind(panic)) | found
+            })
+        })
+    }
+
+    /// Internal function that performs the AOB scan in the given slice, until
+    /// the scan ends or the given flag is set.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `finished` - The flag that stops all the threads when set.
+    /// * `callback` - The callback to execute when a match is found.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    fn scan_until(
+        &self,
+        data: &[u8],
+        finished: &AtomicBool,
+        callback: impl FnMut(usize, &mut ScanControl) + Send + Sync,
+    ) -> bool {
+        // Threads can't know where the matches of the previous chunks end, so
+        // non-overlapping multi-threaded scans must be reconciled after the join.
+        if !self.overlapping && self.threads_for(data.len()) > 1 {
+            self.scan_reconciled(data, finished, callback)
+        } else {
+            self.scan_chunks(data, finished, callback)
+        }
+    }
+
+    /// Internal function that splits the data in a chunk for each thread, and
+    /// scans them in parallel.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `finished` - The flag that stops all the threads when set.
+    /// * `callback` - The callback to execute when a match is found.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    fn scan_chunks(
+        &self,
+        data: &[u8],
+        finished: &AtomicBool,
+        callback: impl FnMut(usize, &mut ScanControl) + Send + Sync,
+    ) -> bool {
+        // Mutex for the callback function.
+        let callback = Mutex::new(callback);
+        let threads = self.threads_for(data.len());
+
+        if threads > 1 {
+            // If the scan is multi-threaded, split the data into chunks and
+            // scan each chunk in parallel.
+
+            // Atomic flag to check if any threads found a match.
+            let found = AtomicBool::new(false);
+
+            // Using a thread scope allows us to pass non 'static references to the threads.
+            // The scope also waits for all the threads to finish before returning.
+            std::thread::scope(|scope| {
+                // Iterate over the number of threads to spawn.
+                for tc in 0..threads {
+                    // Split the data into an overlapped chunks.
+                    // Each thread will scan a chunk of the data.
+                    let range = Self::get_chunk_range(
+                        data.len(),
+                        // Create a chunk for each thread.
+                        threads,
+                        // Overlap the chunks by the length of the signature - 1, to avoid missing
+                        // matches that are split between chunks.
+                        self.signature.len().saturating_sub(1),
+                        tc,
+                    );
+
+                    // Borrow the atomic flag and callback function.
+                    let found = &found;
+                    let callback = &callback;
+
+                    // Spawn a new worker thread.
+                    scope.spawn(move || {
+                        // Scan the chunk of data.
+                        if self.scan_chunk(
+                            data,
+                            range,
+                            tc,
+                            finished,
+                            callback,
+                        ) {
+                            // If a match was found, set the found flag to true.
+                            found.store(true, Ordering::SeqCst);
+                        }
+                    });
+                }
+            });
+
+            // Return true if at least one match was found.
+            found.load(Ordering::SeqCst)
+        } else {
+            // If the scan is single-threaded, avoid the threading clutter and
+            // simply scan the data in the current thread.
+            self.scan_chunk(
+                data,
+                (0, data.len()),
+                0,
+                finished,
+                &callback,
+            )
+        }
+    }
+
+    /// Performs the AOB scan in a list of buffers, which are treated as a single
+    /// contiguous buffer. (e.g. the buffers filled by a vectored read)<br><br>
+    ///
+    /// The matches that span across adjacent buffers are found as well, by scanning
+    /// a small stitched window around each seam.<br>
+    /// Exclusions that fall outside of the buffer (or seam window) containing the
+    /// match are not checked.<br><br>
+    ///
+    /// # Arguments
+    /// * `bufs` - The buffers to scan.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match in the concatenated buffers as an argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_iovecs(
+        &self,
+        bufs: &[&[u8]],
+        mut callback: impl FnMut(usize) -> bool + Send + Sync,
+    ) -> bool {
+        let mut found = false;
+        let mut stopped = false;
+
+        // Offset of the current buffer in the concatenated buffers.
+        let mut base = 0;
+        // Window containing the end of the current buffer and the start of the next ones.
+        let mut window = vec![];
+        // Number of bytes of a match that can fall on each side of a seam.
+        let margin = self.len().saturating_sub(1);
+
+        for (index, buf) in bufs.iter().enumerate() {
+            // Scan the matches that are entirely contained in the buffer.
+            found |= self.scan_control(buf, |offset, control| {
+                if !callback(base + offset) {
+                    stopped = true;
+                    control.stop();
+                }
+            });
+
+            if stopped {
+                break;
+            }
+
+            // Stitch the end of the buffer with the start of the next ones.
+            // The matches found in this window start in the current buffer, and only
+            // the ones that can't end within it were not found by the scan above.
+            // (all of them, unless the pattern has optional bytes)
+            let tail = &buf[buf.len().saturating_sub(margin)..];
+            window.clear();
+            window.extend_from_slice(tail);
+            for next in bufs[index + 1..].iter() {
+                // The window is never extended past the needed bytes.
+                debug_assert!(window.len() <= tail.len() + margin);
+                let needed = tail.len() + margin - window.len();
+                if needed == 0 {
+                    break;
+                }
+
+                window.extend_from_slice(&next[..needed.min(next.len())]);
+            }
+
+            // The window is small, so it is always scanned in the current thread.
+            let window_base = base + buf.len() - tail.len();
+            found |= self.scan_chunk(
+                &window,
+                (0, window.len()),
+                0,
+                &AtomicBool::new(false),
+                &Mutex::new(|offset, control: &mut ScanControl| {
+                    if self.matches_at(tail, offset) {
+                        return;
+                    }
+
+                    if !callback(window_base + offset) {
+                        stopped = true;
+                        control.stop();
+                    }
+                }),
+            );
+
+            if stopped {
+                break;
+            }
+
+            base += buf.len();
+        }
+
+        found
+    }
+
+    /// Performs the AOB scan in a circular buffer, starting from its logical head.<br><br>
+    ///
+    /// The buffer is scanned as if it was rotated to start at `head`, so the end of the
+    /// slice is followed by its start, and the matches that span the wrap point are found.<br>
+    /// The part after the head, the window around the wrap point and the part before the
+    /// head are scanned in this order, like the buffers of [`scan_iovecs`](#method.scan_iovecs).<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data of the circular buffer.
+    /// * `head` - The offset of the logical start of the buffer. (wrapped to the length of the data)
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the logical offset of the match, relative to `head`, as an argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_ring(
+        &self,
+        data: &[u8],
+        head: usize,
+        callback: impl FnMut(usize) -> bool + Send + Sync,
+    ) -> bool {
+        let head = head.checked_rem(data.len()).unwrap_or(0);
+        let (before, after) = data.split_at(head);
+        self.scan_iovecs(&[after, before], callback)
+    }
+
+    /// Performs the AOB scan in the given slice, and collects all the matches.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The offsets of all the matches, sorted in ascending order.
+    pub fn scan_all(&self, data: &[u8]) -> Vec<usize> {
+        let mut offsets = vec![];
+        self.scan(data, |offset| {
+            offsets.push(offset);
+            true
+        });
+
+        // Multi-threaded scans report the matches in no particular order.
+        offsets.sort_unstable();
+        offsets
+    }
+
+    /// Performs the AOB scan in the given slice, and collects all the matches
+    /// in a reference-counted slice.<br><br>
+    ///
+    /// This is useful to share the matches with multiple threads, as cloning
+    /// the result does not copy the offsets.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The offsets of all the matches, sorted in ascending order.
+    pub fn scan_all_shared(&self, data: &[u8]) -> Arc<[usize]> {
+        self.scan_all(data).into()
+    }
+
+    /// Finds the regions of data delimited by the matches of two patterns.<br><br>
+    ///
+    /// For each match of `a`, the first match of `b` that starts after the end of it
+    /// is located, and the bytes between them are reported. The search for the next
+    /// match of `a` then resumes after the end of the match of `b`, so the regions
+    /// never overlap.<br>
+    /// Matches of `a` that are not followed by a match of `b` are skipped.<br><br>
+    ///
+    /// # Arguments
+    /// * `a` - The pattern that marks the start of the regions.
+    /// * `b` - The pattern that marks the end of the regions.
+    /// * `data` - The data slice to scan.
+    /// * `callback` - The callback to execute when a region is found.
+    ///    - The callback receives the range from the end of `a` to the start of `b`.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one region was found, otherwise false.
+    pub fn scan_between(
+        a: &Pattern,
+        b: &Pattern,
+        data: &[u8],
+        mut callback: impl FnMut(Range<usize>) -> bool,
+    ) -> bool {
+        let starts = a.scan_all(data);
+        let ends = b.scan_all(data);
+
+        let mut found = false;
+        let mut ends = ends.into_iter().peekable();
+        let mut resume = 0;
+        for start in starts {
+            if start < resume {
+                continue;
+            }
+
+            let region_start = start + a.len();
+            // Discard the matches of `b` that start before the end of this match of `a`.
+            while ends.next_if(|&end| end < region_start).is_some() {}
+            let region_end = match ends.next() {
+                Some(end) => end,
+                None => break,
+            };
+
+            found = true;
+            if !callback(region_start..region_end) {
+                break;
+            }
+            resume = region_end + b.len();
+        }
+
+        found
+    }
+
+    /// Performs the AOB scan in the given slice, and collects all the matches in
+    /// the given buffer.<br><br>
+    ///
+    /// This works like [`scan_all`](#method.scan_all), but reuses the memory of the
+    /// buffer, which avoids an allocation per scan when scanning many small slices.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `buf` - The buffer that receives the offsets of the matches. Its previous content is discarded.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_all_into(&self, data: &[u8], buf: &mut ScanBuffer) -> bool {
+        let offsets = &mut buf.offsets;
+        offsets.clear();
+        self.scan(data, |offset| {
+            offsets.push(offset);
+            true
+        });
+
+        // Multi-threaded scans report the matches in no particular order.
+        offsets.sort_unstable();
+        !offsets.is_empty()
+    }
+
+    /// Performs the AOB scan in the given slice, and collects all the matches, failing
+    /// if there are too many of them.<br><br>
+    ///
+    /// This is useful to detect patterns that are too broad, as the scan stops as soon
+    /// as the limit is exceeded, in all the threads.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `max_matches` - The maximum number of matches.
+    ///
+    /// # Returns
+    /// The offsets of all the matches, sorted in ascending order.
+    ///
+    /// # Errors
+    /// * `ScanError::TooManyMatches` - The data contains more than `max_matches` matches.
+    pub fn scan_bounded(&self, data: &[u8], max_matches: usize) -> Result<Vec<usize>, ScanError> {
+        let mut offsets = vec![];
+        let mut exceeded = false;
+
+        // The callback is never executed by two threads at the same time, so the
+        // count is exact, and stopping the scan also stops the other threads.
+        self.scan_control(data, |offset, control| {
+            if offsets.len() == max_matches {
+                exceeded = true;
+                control.stop();
+                return;
+            }
+
+            offsets.push(offset);
+        });
+
+        if exceeded {
+            return Err(ScanError::TooManyMatches { cap: max_matches });
+        }
+
+        // Multi-threaded scans report the matches in no particular order.
+        offsets.sort_unstable();
+        Ok(offsets)
+    }
+
+    /// Checks whether the pattern matches the data at the given offset.<br><br>
+    ///
+    /// The exclusions of the pattern are checked, but the options that depend on the
+    /// other matches (non-overlapping matches and minimum distance) are not.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to check.
+    /// * `offset` - The offset of the candidate match, including the leading wildcards.
+    ///
+    /// # Returns
+    /// True if the pattern matches at the given offset, otherwise false.<br>
+    /// Matches that would extend past the end of the data are never reported.
+    pub fn matches_at(&self, data: &[u8], offset: usize) -> bool {
+        // The optional bytes are compared only if they fit in the data.
+        let fits = offset.checked_add(self.min_len()).is_some_and(|end| end <= data.len());
+        !self.is_empty() && fits && self.compare_byte_array(&data[offset + self.start_offset..])
+            && self.check_constraints(data, offset)
+    }
+
+    /// Checks whether the pattern matches the start of the data.<br><br>
+    ///
+    /// This is useful for formats whose header is always at the start of the data,
+    /// as the rest of the data is not scanned. (see [`matches_at`](#method.matches_at))<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to check.
+    ///
+    /// # Returns
+    /// True if the pattern matches at offset `0`, otherwise false.
+    pub fn matches_prefix(&self, data: &[u8]) -> bool {
+        self.matches_at(data, 0)
+    }
+
+    /// Checks whether the pattern matches the data at each of the given offsets.<br><br>
+    ///
+    /// This is useful when the locations of the pattern are known in advance, such as
+    /// the offsets read from a header, as the rest of the data is not scanned.
+    /// (see [`matches_at`](#method.matches_at))<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to check.
+    /// * `offsets` - The offsets of the candidate matches, including the leading wildcards.
+    ///
+    /// # Returns
+    /// The offsets at which the pattern matches, in the order of the candidates.
+    pub fn matches_at_any(&self, data: &[u8], offsets: &[usize]) -> Vec<usize> {
+        offsets.iter()
+            .copied()
+            .filter(|&offset| self.matches_at(data, offset))
+            .collect()
+    }
+
+    /// Checks the pattern at the entries of a table with a fixed stride, such as a
+    /// virtual table or a jump table.<br><br>
+    ///
+    /// Only the start of each entry is checked, which is much faster than scanning
+    /// the whole data when the layout of the table is known.<br>
+    /// The entries that do not fit in the data never match.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice containing the table.
+    /// * `base` - The offset of the first entry of the table.
+    /// * `stride` - The distance in bytes between consecutive entries.
+    /// * `count` - The number of entries of the table.
+    /// * `callback` - The callback to execute when an entry matches.
+    ///    - The callback receives the index of the entry in the table as an argument.
+    ///    - It should return `true` to continue scanning, or `false` to stop.
+    ///
+    /// # Returns
+    /// True if at least one entry matched, otherwise false.
+    pub fn scan_strided(
+        &self,
+        data: &[u8],
+        base: usize,
+        stride: usize,
+        count: usize,
+        mut callback: impl FnMut(usize) -> bool,
+    ) -> bool {
+        let mut found = false;
+        for index in 0..count {
+            let offset = match index.checked_mul(stride).and_then(|delta| delta.checked_add(base)) {
+                Some(offset) => offset,
+                None => break,
+            };
+
+            if self.matches_at(data, offset) {
+                found = true;
+                if !callback(index) {
+                    break;
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Performs the AOB scan in the given slice, and verifies all the matches again
+    /// on the calling thread.<br><br>
+    ///
+    /// The matches are first collected with the regular (and possibly multi-threaded)
+    /// scan, and each of them is then checked with [`matches_at`](#method.matches_at).<br>
+    /// This is slower than [`scan_all`](#method.scan_all), but guards against any issue
+    /// in the parallel scan.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The offsets of the confirmed matches, deduplicated and sorted in ascending order.
+    pub fn scan_verified(&self, data: &[u8]) -> Vec<usize> {
+        let mut offsets = self.scan_all(data);
+        offsets.dedup();
+        offsets.retain(|&offset| self.matches_at(data, offset));
+        offsets
+    }
+
+    /// Checks that the pattern does not match in any of the given samples.<br><br>
+    ///
+    /// This is useful to test a signature against files where it should never be
+    /// found, to catch false positives. The samples are scanned in order, and the
+    /// scan of each sample is stopped at its first match.<br><br>
+    ///
+    /// # Arguments
+    /// * `negatives` - The data slices in which the pattern should not match.
+    ///
+    /// # Returns
+    /// The index of the first sample in which the pattern matches, or `Ok(())` if
+    /// it matches in none of them.
+    ///
+    /// # Example
+    /// ```
+    /// let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 90")
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// let clean: &[u8] = b"\x48\x8B\x05\x91";
+    /// let dirty: &[u8] = b"\x00\x48\x8B\x05\x90";
+    /// assert_eq!(pattern.validate_against(&[clean]), Ok(()));
+    /// assert_eq!(pattern.validate_against(&[clean, dirty]), Err(1));
+    /// ```
+    pub fn validate_against(&self, negatives: &[&[u8]]) -> Result<(), usize> {
+        match negatives.iter().position(|sample| self.scan(sample, |_| false)) {
+            Some(index) => Err(index),
+            None => Ok(()),
+        }
+    }
+
+    /// Performs the AOB scan in the given slice, and finds the first match
+    /// of each block of data.<br><br>
+    ///
+    /// This is useful to process the data in aligned blocks, such as pages.<br>
+    /// The matches that cross the end of a block belong to the block where they start.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `block_size` - The size of the blocks. (`0` is treated as `1`)
+    ///
+    /// # Returns
+    /// The offset of the first match of each block, or `None` if the block does not contain
+    /// the start of a match. The last block may be shorter than the others.
+    pub fn first_per_block(&self, data: &[u8], block_size: usize) -> Vec<Option<usize>> {
+        let block_size = block_size.max(1);
+        let mut blocks = vec![None; data.len().div_ceil(block_size)];
+
+        self.scan(data, |offset| {
+            // Multi-threaded scans report the matches in no particular order.
+            let first: &mut Option<usize> = &mut blocks[offset / block_size];
+            *first = Some(first.map_or(offset, |first| first.min(offset)));
+            true
+        });
+        blocks
+    }
+
+    /// Performs the AOB scan in the given slice, and computes the distances
+    /// between consecutive matches.<br><br>
+    ///
+    /// This is useful to detect structures with a fixed stride, such as arrays
+    /// or virtual tables.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The differences between the sorted offsets of consecutive matches.<br>
+    /// If less than two matches are found, the result is empty.
+    pub fn match_strides(&self, data: &[u8]) -> Vec<usize> {
+        self.scan_all(data)
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .collect()
+    }
+
+    /// Performs the AOB scan in the given slice, and finds the match that is the
+    /// closest to the given offset.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `target` - The offset to which the distance of the matches is measured.
+    ///
+    /// # Returns
+    /// The offset of the match with the smallest absolute distance from `target`, or
+    /// `None` if there are no matches.<br>
+    /// If two matches are equally distant, the one before `target` is returned.
+    pub fn nearest_match(&self, data: &[u8], target: usize) -> Option<usize> {
+        let offsets = self.scan_all(data);
+
+        // The neighbors of the insertion point are the closest matches on each side.
+        let index = offsets.partition_point(|&offset| offset < target);
+        let before = index.checked_sub(1).map(|index| offsets[index]);
+        let after = offsets.get(index).copied();
+
+        match (before, after) {
+            (Some(before), Some(after)) if after - target < target - before => Some(after),
+            (Some(before), _) => Some(before),
+            (None, after) => after,
+        }
+    }
+
+    /// Performs the AOB scan in the given slice, and finds the first match at
+    /// or after the given offset.<br><br>
+    ///
+    /// This is useful to resolve a location relative to a nearby anchor.<br>
+    /// The scan is always single-threaded, and starts directly at `min_offset`, so
+    /// the matches before it are never compared, and they do not suppress the
+    /// later ones in non-overlapping scans.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `min_offset` - The lowest offset of the match.
+    ///
+    /// # Returns
+    /// The offset of the first match that starts at or after `min_offset`, or `None`
+    /// if there are no such matches.
+    pub fn first_after(&self, data: &[u8], min_offset: usize) -> Option<usize> {
+        // The positions of the scan are after the leading wildcards of the matches.
+        let start = min_offset.saturating_add(self.start_offset).min(data.len());
+
+        let mut first = None;
+        self.scan_chunk(
+            data,
+            (start, data.len()),
+            0,
+            &AtomicBool::new(false),
+            &Mutex::new(|offset, control: &mut ScanControl| {
+                first = Some(offset);
+                control.stop();
+            }),
+        );
+        first
+    }
+
+    /// Performs the AOB scan in the given slice, and checks whether a byte is part
+    /// of any match.<br><br>
+    ///
+    /// The span of a match starts at its offset and is [`len`](#method.len) bytes long,
+    /// or shorter if it omits optional bytes, so the trailing wildcards are not included.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `byte_offset` - The offset of the byte to check.
+    ///
+    /// # Returns
+    /// True if the byte is within the span of at least one match, otherwise false.
+    pub fn covers(&self, data: &[u8], byte_offset: usize) -> bool {
+        !self.matches_covering(data, byte_offset).is_empty()
+    }
+
+    /// Performs the AOB scan in the given slice, and finds all the matches whose
+    /// span contains a byte.<br><br>
+    ///
+    /// A byte can be covered by more than one match when the matches overlap.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `byte_offset` - The offset of the byte to check.
+    ///
+    /// # Returns
+    /// The offsets of the matches whose span contains the byte, sorted in ascending order.
+    pub fn matches_covering(&self, data: &[u8], byte_offset: usize) -> Vec<usize> {
+        let offsets = self.scan_all(data);
+
+        // The matches that cover the byte start in `[byte_offset - (len - 1), byte_offset]`.
+        // The matches that omit optional bytes may still end before it.
+        let lowest = byte_offset.saturating_sub(self.len().saturating_sub(1));
+        let start = offsets.partition_point(|&offset| offset < lowest);
+        let end = offsets.partition_point(|&offset| offset <= byte_offset);
+        offsets[start..end].iter()
+            .copied()
+            .filter(|&offset| byte_offset < offset + self.match_len(data, offset))
+            .collect()
+    }
+
+    /// Performs the AOB scan of this pattern and of another one in the given slice,
+    /// and computes the distances between their nearby matches.<br><br>
+    ///
+    /// This is useful to find consistent relationships between the matches, such as
+    /// a field that is always located at the same distance from a marker.<br><br>
+    ///
+    /// # Arguments
+    /// * `other` - The other pattern.
+    /// * `data` - The data slice to scan.
+    /// * `max` - The maximum absolute distance between the matches of a pair.
+    ///
+    /// # Returns
+    /// The signed distances (offset in `other` minus offset in `self`) of all the pairs
+    /// of matches that are at most `max` bytes apart.<br>
+    /// The distances are sorted by the offset of the match of `self`, and then by the
+    /// offset of the match of `other`.
+    pub fn cross_distances(&self, other: &Pattern, data: &[u8], max: usize) -> Vec<isize> {
+        let starts = self.scan_all(data);
+        let ends = other.scan_all(data);
+
+        // Both lists are sorted, so the window of matches of `other` that are close
+        // enough to each match of `self` only moves forward.
+        let mut distances = vec![];
+        let mut first = 0;
+        for a in starts {
+            let low = a.saturating_sub(max);
+            while ends.get(first).is_some_and(|&b| b < low) {
+                first += 1;
+            }
+
+            let high = a.saturating_add(max);
+            distances.extend(
+                ends[first..].iter()
+                    .take_while(|&&b| b <= high)
+                    .map(|&b| b as isize - a as isize),
+            );
+        }
+
+        distances
+    }
+
+    /// Computes how selective the primary anchor of the pattern is in the given data.<br><br>
+    ///
+    /// The scanner only compares the whole signature at the positions where its
+    /// anchors match, so this measures how effective the first of these filters is.<br>
+    /// If the scans search for the longest fixed fragment of the pattern, this is the
+    /// fraction of the positions where the whole fragment matches.<br>
+    /// A value near `1.0` means that the anchor is common in the data, and that almost
+    /// every position requires a full comparison.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to analyze.
+    ///
+    /// # Returns
+    /// The fraction of the candidate positions where the anchor matched, in the range `0.0..=1.0`.<br>
+    /// Patterns without fixed bytes have no anchor, so they always return `1.0`, while
+    /// data shorter than the pattern returns `0.0`.
+    pub fn anchor_selectivity(&self, data: &[u8]) -> f32 {
+        let last = match data.len().checked_sub(self.len()) {
+            Some(last) => last,
+            None => return 0.0,
+        };
+
+        if let Some(Fragment { range, finder }) = self.fragment.as_ref() {
+            // The fragments may overlap, so the search restarts after the start of each hit.
+            let start = self.start_offset + range.start;
+            let candidates = &data[start..start + last + range.len()];
+            let mut hits = 0;
+            let mut position = 0;
+            while let Some(distance) = finder.find(&candidates[position..]) {
+                hits += 1;
+                position += distance + 1;
+            }
+            return hits as f32 / (last + 1) as f32;
+        }
+
+        match self.anchor {
+            Some(anchor) => {
+                // The signature starts after the leading wildcards.
+                let start = self.start_offset + anchor.index;
+                let candidates = &data[start..=start + last];
+                let hits = candidates.iter().filter(|&&byte| byte & anchor.bits == anchor.value).count();
+                hits as f32 / candidates.len() as f32
+            }
+            None => 1.0,
+        }
+    }
+
+    /// Computes a score of how reliable the pattern is for locating a single
+    /// position in the given data, to compare candidate signatures.<br><br>
+    ///
+    /// The score is calculated as `specificity / matches`, where:
+    /// - `specificity` is the number of fixed bits divided by the number of bits of
+    ///   the pattern, including the leading wildcards. (trailing wildcards are discarded
+    ///   when the pattern is created)
+    /// - `matches` is the number of matches of the pattern in the data.
+    ///
+    /// A unique match of a pattern without wildcards scores `1.0`, and patterns
+    /// without matches score `0.0`.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The confidence score, in the range `0.0..=1.0`.
+    pub fn confidence(&self, data: &[u8]) -> f32 {
+        let mut matches = 0usize;
+        self.scan(data, |_| {
+            matches += 1;
+            true
+        });
+
+        if matches == 0 || self.is_empty() {
+            return 0.0;
+        }
+
+        let fixed = self.mask.iter().map(|bits| bits.count_ones()).sum::<u32>();
+        let specificity = fixed as f32 / (self.len() * 8) as f32;
+        specificity / matches as f32
+    }
+
+    /// Computes the fraction of the candidate positions of the data that match the pattern.<br><br>
+    ///
+    /// A high density means that the pattern is too broad to locate anything.<br>
+    /// The candidate positions are the ones where the whole pattern fits in the data.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The number of matches divided by the number of candidate positions, in the
+    /// range `0.0..=1.0`.<br>
+    /// Data shorter than the pattern has no candidate positions, so it returns `0.0`.
+    pub fn density(&self, data: &[u8]) -> f64 {
+        let candidates = match data.len().checked_sub(self.len()) {
+            Some(last) => last + 1,
+            None => return 0.0,
+        };
+
+        let mut matches = 0usize;
+        self.scan(data, |_| {
+            matches += 1;
+            true
+        });
+
+        matches as f64 / candidates as f64
+    }
+
+    /// Computes the average number of signature bytes that are compared at each
+    /// candidate position of the data, before finding a mismatch or a match.<br><br>
+    ///
+    /// This shows whether the order of the bytes of the signature causes long comparisons,
+    /// as the bytes are compared from the first one, and the comparison stops at the first
+    /// mismatch. A pattern that starts with a rare byte compares about one byte per position.<br>
+    /// The candidate positions are the ones where the whole pattern fits in the data, and the
+    /// anchors are not used to skip any of them.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to analyze.
+    ///
+    /// # Returns
+    /// The average number of bytes compared per candidate position, in the range
+    /// `1.0..=len`, where `len` excludes the leading wildcards.<br>
+    /// Data shorter than the pattern has no candidate positions, so it returns `0.0`.
+    pub fn avg_compare_length(&self, data: &[u8]) -> f64 {
+        if self.signature.is_empty() || data.len() < self.len() {
+            return 0.0;
         }
+
+        let windows = data[self.start_offset..].windows(self.signature.len());
+        let candidates = windows.len();
+        let compared = windows
+            .map(|window| {
+                // The mismatching byte is compared as well.
+                window.iter()
+                    .zip(self.signature.iter().zip(&self.mask))
+                    .position(|(&byte, (&sig, &bits))| byte & bits != sig)
+                    .map_or(self.signature.len(), |index| index + 1)
+            })
+            .sum::<usize>();
+
+        compared as f64 / candidates as f64
     }
 
+    /// Estimates how long a scan of this pattern would take on data of the given length.<br><br>
+    ///
+    /// This is a rough estimate, meant for progress bars and ETAs. It is based on the
+    /// throughput of a small benchmark, which is measured the first time this function is
+    /// called, and on the number of threads of the pattern.<br>
+    /// The actual duration depends on the data and on the signature, as common anchor
+    /// bytes and many matches make the scans slower.<br><br>
+    ///
+    /// # Arguments
+    /// * `data_len` - The length of the data to scan.
+    ///
     /// # Returns
-    /// The number of threads to use in scans of this pattern.
-    pub fn get_threads(&self) -> usize {
-        self.threads
+    /// The estimated duration of the scan.
+    pub fn estimate_duration(&self, data_len: usize) -> Duration {
+        static THROUGHPUT: OnceLock<f64> = OnceLock::new();
+
+        let throughput = *THROUGHPUT.get_or_init(|| {
+            // Fill the data with pseudo-random bytes, so that the anchors behave
+            // as they would with real data.
+            let mut state = 0x2545F491u32;
+            let data = (0..CALIBRATION_SIZE)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 17;
+                    state ^= state << 5;
+                    state as u8
+                })
+                .collect::<Vec<_>>();
+
+            let pattern = Self::new(
+                vec![0x48, 0x8B, 0x05, 0x00, 0x00, 0x00, 0x00, 0xE8],
+                vec![true, true, true, false, false, false, false, true],
+                1,
+            );
+
+            let start = std::time::Instant::now();
+            pattern.scan(&data, |_| true);
+            // Avoid dividing by zero with timers that have a low resolution.
+            let elapsed = start.elapsed().max(Duration::from_micros(1));
+            CALIBRATION_SIZE as f64 / elapsed.as_secs_f64()
+        });
+
+        let threads = self.threads_for(data_len).max(1) as f64;
+        Duration::from_secs_f64(data_len as f64 / (throughput * threads))
     }
 
-    /// Performs the AOB scan in the given slice.<br><br>
+    /// Performs the AOB scan in the given slice, and returns a page of the sorted
+    /// match offsets.<br><br>
     ///
-    /// If specified, this function will split the data into chunks and scan
-    /// each chunk in parallel.<br><br>
+    /// This is useful to display a large number of matches in a user interface.<br>
+    /// Only the offsets up to the end of the requested page are kept in memory, but
+    /// the data before the page must still be scanned, so deeper pages are slower to
+    /// compute. Single-threaded scans also stop as soon as the page is complete, while
+    /// multi-threaded scans always scan the whole data, as the matches are not found
+    /// in order.<br><br>
     ///
     /// # Arguments
     /// * `data` - The data slice to scan.
-    /// * `callback` - The callback to execute when a match is found.
-    ///    - The callback receives the offset of the match as an argument.
-    ///    - It should return `true` to continue scanning, or `false` to stop.
+    /// * `page` - The index of the page, starting from `0`.
+    /// * `page_size` - The maximum number of offsets in a page.
     ///
     /// # Returns
-    /// True if at least one match was found, otherwise false.
-    pub fn scan(
-        &self,
-        data: &[u8],
-        callback: impl FnMut(usize) -> bool + Send + Sync,
-    ) -> bool {
-        // Atomic flag to stop all threads if a match is found and accepted.
-        let finished = Arc::new(AtomicBool::new(false));
-        // Mutex for the callback function.
-        let callback_arc = Arc::new(Mutex::new(callback));
-
-        if self.threads > 1 {
-            // If the scan is multi-threaded, split the data into chunks and
-            // scan each chunk in parallel.
-
-            // Count the number of running threads, so we can wait for them to finish.
-            let running_threads = Arc::new(AtomicUsize::new(0));
-            // Atomic flag to check if any threads found a match.
-            let found = Arc::new(AtomicBool::new(false));
+    /// The offsets of the matches in the page, sorted in ascending order, and whether
+    /// there are more matches after the page.
+    pub fn scan_page(&self, data: &[u8], page: usize, page_size: usize) -> (Vec<usize>, bool) {
+        let start = page.saturating_mul(page_size);
+        // One more match is needed to know whether there is a next page.
+        let limit = start.saturating_add(page_size).saturating_add(1);
 
-            // Using a thread scope allows us to pass non 'static references to the threads.
-            std::thread::scope(|scope| {
-                // Iterate over the number of threads to spawn.
-                for tc in 0..self.threads {
-                    // Split the data into an overlapped chunks.
-                    // Each thread will scan a chunk of the data.
-                    let range = Self::get_chunk_range(
-                        data.len(),
-                        // Create a chunk for each thread.
-                        self.threads,
-                        // Overlap the chunks by the length of the signature - 1, to avoid missing
-                        // matches that are split between chunks.
-                        self.signature.len() - 1,
-                        tc,
-                    );
+        // Max-heap of the lowest offsets found so far.
+        let mut lowest = BinaryHeap::new();
+        let single_threaded = self.threads_for(data.len()) <= 1;
 
-                    // Clone the atomic flags and callback function.
-                    let running_threads = running_threads.clone();
-                    let finished = finished.clone();
-                    let found = found.clone();
-                    let callback = callback_arc.clone();
+        self.scan_control(data, |offset, control| {
+            lowest.push(offset);
+            if lowest.len() > limit {
+                lowest.pop();
+            }
 
-                    // Spawn a new worker thread and increment the atomic running thread count.
-                    running_threads.fetch_add(1, Ordering::SeqCst);
-                    scope.spawn(move || {
-                        // Resize the slice to the chunk region.
-                        let data = &data[range.0..range.1];
+            // Single-threaded scans find the matches in order, so the page is complete.
+            if single_threaded && lowest.len() == limit {
+                control.stop();
+            }
+        });
 
-                        // Scan the chunk of data.
-                        if self.scan_chunk(
-                            data,
-                            range.0,
-                            &finished,
-                            callback,
-                        ) {
-                            // If a match was found, set the found flag to true.
-                            found.store(true, Ordering::SeqCst);
-                        }
+        let offsets = lowest.into_sorted_vec();
+        let has_more = offsets.len() == limit;
+        // The limit is at least 1, as it saturates instead of wrapping.
+        debug_assert!(limit > 0);
+        let end = offsets.len().min(limit - 1);
+        (offsets.get(start..end).unwrap_or_default().to_vec(), has_more)
+    }
 
-                        // Thread has finished, decrement the atomic running thread count.
-                        running_threads.fetch_sub(1, Ordering::SeqCst);
-                    });
+    /// Performs the AOB scan in the given slice, and collects all the matches
+    /// along with their span and the matched bytes.<br><br>
+    ///
+    /// This is more convenient but also more expensive than
+    /// [`scan_all`](#method.scan_all), as the matched bytes are copied.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// All the matches, sorted by offset in ascending order.
+    pub fn scan_matches(&self, data: &[u8]) -> Vec<Match> {
+        self.scan_all(data)
+            .into_iter()
+            .map(|offset| {
+                let end = offset + self.match_len(data, offset);
+                Match {
+                    offset,
+                    end,
+                    bytes: data[offset..end].to_vec(),
                 }
-            });
+            })
+            .collect()
+    }
 
-            // Spin wait until all threads have finished.
-            while running_threads.load(Ordering::SeqCst) != 0 {
-                std::thread::sleep(std::time::Duration::from_millis(1));
-            }
+    /// Performs the AOB scan in the given slice, and extracts a region of data
+    /// starting at each match.<br><br>
+    ///
+    /// Regions of matches near the end of the data are clamped to the end of the
+    /// slice, so they may be shorter than `region_len`.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `region_len` - The length of the region to extract at each match.
+    ///
+    /// # Returns
+    /// The regions starting at each match, sorted by offset in ascending order.
+    pub fn carve<'a>(&self, data: &'a [u8], region_len: usize) -> Vec<&'a [u8]> {
+        self.scan_all(data)
+            .into_iter()
+            .map(|offset| &data[offset..offset.saturating_add(region_len).min(data.len())])
+            .collect()
+    }
 
-            // Return true if at least one match was found.
-            found.load(Ordering::SeqCst)
-        } else {
-            // If the scan is single-threaded, avoid the threading clutter and
-            // simply scan the data in the current thread.
-            self.scan_chunk(
-                data,
-                0,
-                &finished,
-                callback_arc,
-            )
+    /// Performs the AOB scan in the given slice, and transforms a region of data
+    /// starting at each match in place.<br><br>
+    ///
+    /// This is useful to decode the data found by the scan, such as XOR-obfuscated blobs.<br>
+    /// All the matches are found in the original data before any region is transformed,
+    /// and then the transform is applied on the calling thread, one region at a time,
+    /// in ascending order of offset.<br>
+    /// If the regions overlap, the overlapping bytes are transformed multiple times, each
+    /// time receiving the result of the previous transform. Regions of matches near the
+    /// end of the data are clamped to the end of the slice.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan and transform.
+    /// * `region_len` - The length of the region to transform at each match.
+    /// * `transform` - The function that transforms the region of each match.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    pub fn scan_transform(
+        &self,
+        data: &mut [u8],
+        region_len: usize,
+        transform: impl Fn(&mut [u8]),
+    ) -> bool {
+        let offsets = self.scan_all(data);
+        for &offset in &offsets {
+            let end = offset.saturating_add(region_len).min(data.len());
+            transform(&mut data[offset..end]);
         }
+
+        !offsets.is_empty()
     }
 
     /// Internal function that calculates the overlapped
     /// data range between N chunks.<br><br>
     ///
-    /// This is used to split the data into chunks to give to each thread.<br><br>
+    /// This is used to split the data into chunks to give to each thread.<br>
+    /// Each chunk owns the positions between its start and the start of the next
+    /// chunk, and it is extended at the end so that matches starting in its last
+    /// positions can be compared entirely.<br>
+    /// This way, every position is scanned by exactly one chunk, and no match is
+    /// reported twice.<br><br>
     ///
     /// # Arguments
     /// * `data_size` - The length of the total data.
     /// * `chunks` - The number of chunks to split the data into.
-    /// * `overlap` - The number of bytes to overlap between chunks. (at start and end)
+    /// * `overlap` - The number of bytes to overlap with the next chunk. (at the end)
     /// * `index` - The index of the chunk to calculate the range for.
     ///
     /// # Returns
@@ -183,6 +2457,7 @@ impl Pattern {
         overlap: usize,
         index: usize,
     ) -> (usize, usize) {
+        debug_assert!(index < chunks);
         let chunk_size = data_size / chunks;
         let remainder = data_size % chunks;
 
@@ -191,16 +2466,12 @@ impl Pattern {
 
         // End points to the end of the new chunk data.
         // If this is the last chunk, add the remainder to the end.
-        let mut end = start + chunk_size + if index == chunks - 1 { remainder } else { 0 };
-
-        // Overlap the chunks by the length of the signature - 1.
-        // This is to avoid missing matches that are split between chunks.
-        let start = start - if start >= overlap { overlap } else { 0 };
+        let end = start + chunk_size + if index == chunks - 1 { remainder } else { 0 };
 
-        // If this is the last chunk, don't overlap the end.
-        end = end + if end < data_size - overlap { overlap } else { 0 };
-
-        (start, end)
+        // Overlap the end of the chunk with the start of the next one, by the length
+        // of the signature - 1. This is to avoid missing matches that are split between
+        // chunks. The last chunk already ends with the data, so it is not extended.
+        (start, end.saturating_add(overlap).min(data_size))
     }
 
     /// Internal function that scans a chunk of data for the pattern.<br><br>
@@ -212,8 +2483,9 @@ impl Pattern {
     /// received `false` from the callback)<br><br>
     ///
     /// # Arguments
-    /// * `data` - The data to scan.
-    /// * `chunk_offset` - Starting offset of the chunk, used to calculate the absolute match address.
+    /// * `data` - The whole data slice, used to check the constraints around the matches.
+    /// * `range` - The start and end of the chunk to scan, used to calculate the absolute match address.
+    /// * `thread` - The index of the worker thread that scans the chunk, passed to the callback.
     /// * `finished` - The atomic flag used to exit the loop early.
     /// * `callback` - The callback to execute when a match is found.
     ///
@@ -223,56 +2495,312 @@ impl Pattern {
     fn scan_chunk(
         &self,
         data: &[u8],
-        chunk_offset: usize,
-        finished: &Arc<AtomicBool>,
-        callback: Arc<Mutex<impl FnMut(usize) -> bool + Send + Sync>>,
+        range: (usize, usize),
+        thread: usize,
+        finished: &AtomicBool,
+        callback: &Mutex<impl FnMut(usize, &mut ScanControl) + Send + Sync>,
     ) -> bool {
-        // Size of the scan to perform.
-        let length = data.len() - self.signature.len();
+        let chunk_offset = range.0;
+        let chunk = &data[range.0..range.1];
+
+        // Empty patterns can only be created with mismatched constructor arguments,
+        // and there is nothing to compare.
+        if self.signature.is_empty() {
+            return false;
+        }
 
-        // Store the first byte of the signature to compare it with the data.
-        // This byte is always not masked due to the optimizations in the pattern
-        // creation function, so we can use it to speed up the search.
-        let first_byte = self.signature[0];
-        let first_mask = self.mask[0];
+        // Number of positions to scan. (the last one is where the signature ends with the data)
+        // If the data is shorter than the signature, there is nothing to scan.
+        // The positions near the end of the data can still contain the matches that omit
+        // the optional bytes, while in the other chunks they belong to the next chunk.
+        let shortest = if range.1 == data.len() {
+            self.signature.len() - self.optional.len()
+        } else {
+            self.signature.len()
+        };
+        let length = match chunk.len().checked_sub(shortest) {
+            Some(last) => last + 1,
+            None => return false,
+        };
 
         // Result of the scan function.
         // This is only relative to this chunk, and is used to determine
         // if at least one match was found in the current function.
         let mut found = false;
 
+        // Offset of the last match reported in this chunk.
+        let mut last_reported: Option<usize> = None;
+
+        // Position and hash of the last window of data hashed by the rolling hash.
+        let mut rolling_window: Option<(usize, u64)> = None;
+
+        // Patterns made of a single fixed byte are a plain byte search, so their
+        // occurrences are iterated directly, and the signature is never compared.
+        let mut byte_hits = match (self.signature.as_slice(), self.mask.as_slice()) {
+            ([byte], [0xFF]) if self.optional.is_empty() => Some(memchr::memchr_iter(*byte, &chunk[..length])),
+            _ => None,
+        };
+
         // Iterate over all the scan data.
-        for i in 0..length {
+        // The positions before the leading wildcards can't be the start of a
+        // match, as the wildcards would fall outside of the data.
+        let mut i = self.start_offset.saturating_sub(chunk_offset);
+        while i < length {
             // If the running flag is set to false, stop the scan.
             // This is used to stop all threads if a match is found.
             if finished.load(Ordering::Relaxed) {
                 return found;
             }
 
-            // If the first byte matches, compare the rest of the signature,
-            // otherwise directly skip to the next byte.
-            //
-            // We also check for the first mask so that in the case of a pattern
-            // with all wildcards, we don't skip the first byte.
-            // If the pattern contains at least one non-wildcard byte, the first
-            // byte will never be masked.
-            if data[i] != first_byte && first_mask {
+            // Skip directly to the next position where the hash of the data is the
+            // same as the hash of the signature, where the longest fixed fragment
+            // matches, or where the primary anchor matches.
+            // Patterns made entirely of wildcards have no anchor, so every
+            // position is compared.
+            if let Some(target) = self.rolling_hash {
+                match self.next_hash_hit(chunk, i, length, target, &mut rolling_window) {
+                    Some(next) => i = next,
+                    None => break,
+                }
+            } else if let Some(hits) = byte_hits.as_mut() {
+                // The positions skipped by the callback are also skipped by the iterator.
+                match hits.find(|&hit| hit >= i) {
+                    Some(next) => i = next,
+                    None => break,
+                }
+            } else if let Some(Fragment { range, finder }) = self.fragment.as_ref() {
+                // The longest fixed fragment is searched as a substring, and the signature
+                // is only compared at the positions that it implies.
+                // The fragment of the last position ends within the chunk, as it
+                // is never after the first optional byte.
+                let window = &chunk[i + range.start..length - 1 + range.end];
+                match finder.find(window) {
+                    Some(distance) => i += distance,
+                    None => break,
+                }
+            } else if let Some(anchor) = self.anchor {
+                let window = &chunk[i + anchor.index..length + anchor.index];
+                let next = if anchor.bits == 0xFF {
+                    memchr::memchr(anchor.value, window)
+                } else {
+                    window.iter().position(|&byte| byte & anchor.bits == anchor.value)
+                };
+
+                match next {
+                    Some(distance) => i += distance,
+                    None => break,
+                }
+            }
+
+            // Position of the current candidate.
+            let position = i;
+            i += 1;
+
+            // If the secondary anchor matches, compare the rest of the signature,
+            // otherwise directly skip to the next candidate.
+            if self.secondary_anchor.is_some_and(|anchor| {
+                chunk[position + anchor.index] & anchor.bits != anchor.value
+            }) {
                 continue;
             }
 
-            if self.compare_byte_array(&data[i..]) {
+            if byte_hits.is_some() || self.compare_byte_array(&chunk[position..]) {
+                // The scan starts after the leading wildcards, so the match can't
+                // start before the data.
+                debug_assert!(chunk_offset + position >= self.start_offset);
+                let offset = chunk_offset + position - self.start_offset;
+
+                // Discard the match if it does not satisfy the additional constraints.
+                if !self.check_constraints(data, offset) {
+                    continue;
+                }
+
+                // Suppress the match if it is too close to the last reported one.
+                // The positions are scanned in ascending order, so it is always after it.
+                debug_assert!(last_reported.is_none_or(|last| last < offset));
+                if last_reported.is_some_and(|last| offset - last < self.min_distance) {
+                    continue;
+                }
+                last_reported = Some(offset);
+
                 // Acquire the mutex and run the scan callback function.
                 // We need to lock the mutex to prevent multiple threads from
                 // running the callback at the same time.
                 // This should not impact performance too much, as the callback
                 // is only executed when a match is found.
                 found = true;
-                if !callback.lock().unwrap().deref_mut()(chunk_offset + i - self.start_offset) {
-                    // If the callback returns false, stop scanning bet.
+                let mut control = ScanControl { thread, ..ScanControl::default() };
+                {
+                    let mut callback = match callback.lock() {
+                        Ok(callback) => callback,
+                        // The mutex is poisoned if the callback panicked in another
+                        // thread. That panic is propagated when the threads are joined,
+                        // so stop scanning instead of running the callback again.
+                        Err(_) => {
+                            finished.store(true, Ordering::Relaxed);
+                            break;
+                        }
+                    };
+
+                    // Stop the other threads if the callback panics in this one.
+                    let _guard = FinishOnPanic(finished);
+                    callback.deref_mut()(offset, &mut control);
+                }
+
+                if control.stopped {
+                    // If the callback stopped the scan, stop scanning bet.
                     finished.store(true, Ordering::Relaxed);
                     break;
                 }
+
+                // Move the cursor past the positions the callback wants to skip.
+                i = i.saturating_add(control.skip);
+
+                // If overlapping matches are not allowed, move the cursor past the match.
+                if !self.overlapping {
+                    i = i.max(position + self.len());
+                }
+            }
+        }
+
+        found
+    }
+
+    /// Internal function that finds the next position of a chunk where the rolling
+    /// hash of the data is equal to the hash of the signature.<br><br>
+    ///
+    /// The hash of the last window is kept between the calls, so that consecutive
+    /// searches only hash each byte once. If the search starts too far from the last
+    /// window, the hash is computed again.<br><br>
+    ///
+    /// # Arguments
+    /// * `chunk` - The chunk of data to scan.
+    /// * `from` - The first position to check.
+    /// * `length` - The number of positions in the chunk.
+    /// * `target` - The hash of the signature.
+    /// * `window` - The position and hash of the last hashed window.
+    ///
+    /// # Returns
+    /// The position of the next hash hit, or `None` if there are no more hits in the chunk.
+    fn next_hash_hit(
+        &self,
+        chunk: &[u8],
+        from: usize,
+        length: usize,
+        target: u64,
+        window: &mut Option<(usize, u64)>,
+    ) -> Option<usize> {
+        let size = self.signature.len();
+        // Weight of the byte that leaves the window. (ROLLING_HASH_BASE ^ (size - 1))
+        let weight = ROLLING_HASH_BASE.wrapping_pow(size as u32 - 1);
+
+        if from >= length {
+            return None;
+        }
+
+        let (mut position, mut hash) = match *window {
+            Some((position, hash)) if position <= from && from - position < size => (position, hash),
+            _ => (from, Self::hash_window(&chunk[from..from + size])),
+        };
+
+        loop {
+            if position >= from && hash == target {
+                *window = Some((position, hash));
+                return Some(position);
+            }
+
+            // Roll the window forward by one byte.
+            if position + 1 >= length {
+                *window = None;
+                return None;
+            }
+            hash = hash
+                .wrapping_sub((chunk[position] as u64).wrapping_mul(weight))
+                .wrapping_mul(ROLLING_HASH_BASE)
+                .wrapping_add(chunk[position + size] as u64);
+            position += 1;
+        }
+    }
+
+    /// Internal function that checks the constraints of the pattern that are
+    /// not expressed by its signature, such as the exclusions and the checksums.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The whole data slice.
+    /// * `offset` - The offset of a match of the signature in the data.
+    ///
+    /// # Returns
+    /// True if the match satisfies all the constraints, false otherwise.
+    fn check_constraints(&self, data: &[u8], offset: usize) -> bool {
+        // Exclusions outside of the data are never triggered.
+        let excluded = self.exclusions.iter().any(|&(relative_offset, byte)| {
+            offset.checked_add_signed(relative_offset)
+                .and_then(|position| data.get(position))
+                .is_some_and(|&value| value == byte)
+        });
+
+        // Checksums of ranges outside of the data can't be verified, so they reject the match.
+        #[cfg(feature = "checksum")]
+        let excluded = excluded || self.checksums.iter().any(|(range, expected)| {
+            let bytes = offset.checked_add(range.start)
+                .zip(offset.checked_add(range.end))
+                .and_then(|(start, end)| data.get(start..end));
+            bytes.is_none_or(|bytes| crc32fast::hash(bytes) != *expected)
+        });
+
+        !excluded
+    }
+
+    /// Internal function that performs a non-overlapping multi-threaded scan, reporting
+    /// the same matches as a single-threaded scan regardless of the thread scheduling.<br><br>
+    ///
+    /// All the matches are collected by the threads first, and the non-overlapping rule
+    /// is then applied to them in ascending order, on the calling thread.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `finished` - The flag that stops all the threads when set.
+    /// * `callback` - The callback to execute when a match is found.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    fn scan_reconciled(
+        &self,
+        data: &[u8],
+        finished: &AtomicBool,
+        mut callback: impl FnMut(usize, &mut ScanControl) + Send + Sync,
+    ) -> bool {
+        // Collect all the matches, including the ones that will be suppressed.
+        let collector = Self {
+            overlapping: true,
+            min_distance: 0,
+            ..self.clone()
+        };
+
+        let mut offsets = vec![];
+        collector.scan_chunks(data, finished, |offset, _| offsets.push(offset));
+        offsets.sort_unstable();
+
+        // Apply the same rules as the single-threaded scan, in ascending order.
+        let mut found = false;
+        let mut next = 0;
+        for offset in offsets {
+            if offset < next {
+                continue;
+            }
+
+            found = true;
+            let mut control = ScanControl::default();
+            callback(offset, &mut control);
+
+            if control.stopped {
+                break;
             }
+
+            next = (offset + 1)
+                .saturating_add(control.skip)
+                .max(offset + self.len())
+                .max(offset.saturating_add(self.min_distance));
         }
 
         found
@@ -280,21 +2808,41 @@ impl Pattern {
 
     /// Internal function that scans for the pattern in a chunk of data.<br><br>
     ///
+    /// With the `sse2` feature, the signatures of 2 to 16 bytes without optional bytes
+    /// are compared with a single SSE2 comparison on x86 targets.<br><br>
+    ///
     /// # Arguments
     /// * `data` - The data to scan for the pattern.
     ///
     /// # Returns
     /// True if the pattern was found in the data, false otherwise.
     fn compare_byte_array(&self, data: &[u8]) -> bool {
-        for (i, sig) in self.signature.iter().enumerate() {
-            // If the mask is false, it means that the byte is a wildcard.
-            // We can skip it.
-            if !self.mask[i] {
-                continue;
-            }
+        if !self.optional.is_empty() {
+            return self.compare_optional(data, 0, 0).is_some();
+        }
+
+        #[cfg(all(feature = "sse2", any(target_arch = "x86", target_arch = "x86_64"), target_feature = "sse2"))]
+        if let Some((signature, mask)) = &self.short {
+            debug_assert!(data.len() >= self.signature.len());
+            return simd::compare_short(data, signature, mask);
+        }
+
+        for run in &self.runs {
+            let signature = &self.signature[run.range.clone()];
+            let data = &data[run.range.clone()];
 
-            // If the byte does not match the signature, return false.
-            if data[i] != *sig {
+            // The fully fixed runs are compared directly, while in the others only the
+            // bits set in the mask are compared.
+            // If the bytes do not match the signature, return false.
+            let matches = if run.exact {
+                data == signature
+            } else {
+                data.iter()
+                    .zip(signature.iter().zip(&self.mask[run.range.clone()]))
+                    .all(|(&byte, (&sig, &bits))| byte & bits == sig)
+            };
+
+            if !matches {
                 return false;
             }
         }
@@ -302,6 +2850,70 @@ impl Pattern {
         // If we reach this point, it means that the byte array matches the signature.
         true
     }
+
+    /// Internal function that compares a signature with optional bytes with the data,
+    /// trying both the presence and the absence of each optional byte.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data to compare, which may be shorter than the signature.
+    /// * `sig_index` - The index of the first byte of the signature to compare.
+    /// * `data_index` - The index of the data byte to compare it with.
+    ///
+    /// # Returns
+    /// The number of data bytes covered by the signature if the rest of the signature
+    /// matches the data, or `None` otherwise.
+    fn compare_optional(&self, data: &[u8], mut sig_index: usize, mut data_index: usize) -> Option<usize> {
+        while sig_index < self.signature.len() {
+            let matches = data.get(data_index)
+                .is_some_and(|&byte| byte & self.mask[sig_index] == self.signature[sig_index]);
+
+            if self.is_optional(sig_index) {
+                // Prefer the matches that contain the optional byte.
+                return matches
+                    .then(|| self.compare_optional(data, sig_index + 1, data_index + 1))
+                    .flatten()
+                    .or_else(|| self.compare_optional(data, sig_index + 1, data_index));
+            }
+
+            if !matches {
+                return None;
+            }
+
+            sig_index += 1;
+            data_index += 1;
+        }
+
+        Some(data_index)
+    }
+
+    /// Internal function that computes the length of a match of the pattern, which is
+    /// shorter than [`len`](#method.len) if it omits some optional bytes.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The whole data slice.
+    /// * `offset` - The offset of a match of the pattern in the data.
+    ///
+    /// # Returns
+    /// The number of bytes covered by the match, including the leading wildcards.
+    fn match_len(&self, data: &[u8], offset: usize) -> usize {
+        if self.optional.is_empty() {
+            return self.len();
+        }
+
+        let start = (offset + self.start_offset).min(data.len());
+        self.start_offset + self.compare_optional(&data[start..], 0, 0).unwrap_or(self.signature.len())
+    }
+
+    /// Internal function that checks whether a byte of the signature is optional.<br><br>
+    ///
+    /// # Arguments
+    /// * `index` - The index of the byte in the signature.
+    ///
+    /// # Returns
+    /// True if the byte is optional, false otherwise.
+    fn is_optional(&self, index: usize) -> bool {
+        self.optional.binary_search(&index).is_ok()
+    }
 }
 
 impl std::fmt::Display for Pattern {
@@ -315,7 +2927,7 @@ impl std::fmt::Display for Pattern {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "[ ")?;
         for (i, byte) in self.signature.iter().enumerate() {
-            if self.mask[i] {
+            if self.mask[i] == 0xFF {
                 write!(f, "{:02X} ", byte)?;
             } else {
                 write!(f, "? ")?;
@@ -324,3 +2936,119 @@ impl std::fmt::Display for Pattern {
         write!(f, "] [t={}]", self.threads)
     }
 }
+
+/// A key that identifies what a pattern matches, without the thread count.
+/// (see [`Pattern::match_key`](struct.Pattern.html#method.match_key))
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MatchKey {
+    signature: Vec<u8>,
+    mask: Vec<u8>,
+    start_offset: usize,
+    exclusions: Vec<(isize, u8)>,
+    checksums: Vec<(Range<usize>, u32)>,
+    optional: Vec<usize>,
+    overlapping: bool,
+    min_distance: usize,
+}
+
+/// A match found by [`Pattern::scan_matches`](struct.Pattern.html#method.scan_matches).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    /// The offset of the first byte of the match in the scanned data.
+    pub offset: usize,
+
+    /// The offset of the byte after the last byte of the match in the scanned data.
+    pub end: usize,
+
+    /// The bytes of the scanned data in the range of the match. (`offset..end`)
+    pub bytes: Vec<u8>,
+}
+
+/// A reusable buffer for the offsets collected by [`Pattern::scan_all_into`](struct.Pattern.html#method.scan_all_into).<br><br>
+///
+/// The buffer is cleared at the start of each scan, but keeps its capacity, so
+/// repeated scans only allocate when they find more matches than any previous one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanBuffer {
+    offsets: Vec<usize>,
+}
+
+impl ScanBuffer {
+    /// Creates an empty buffer.<br><br>
+    ///
+    /// # Returns
+    /// The newly created buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty buffer that can hold the given number of offsets without
+    /// allocating.<br><br>
+    ///
+    /// # Arguments
+    /// * `capacity` - The number of offsets to allocate.
+    ///
+    /// # Returns
+    /// The newly created buffer.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            offsets: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// # Returns
+    /// The offsets collected by the last scan, sorted in ascending order.
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    /// # Returns
+    /// The number of offsets the buffer can hold without allocating.
+    pub fn capacity(&self) -> usize {
+        self.offsets.capacity()
+    }
+}
+
+/// Guard that sets the `finished` flag of a scan if it is dropped while
+/// the thread is unwinding, so that the other threads stop scanning.
+struct FinishOnPanic<'a>(&'a AtomicBool);
+
+impl Drop for FinishOnPanic<'_> {
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+/// Controls the flow of a scan from within the callback of
+/// [`Pattern::scan_control`](struct.Pattern.html#method.scan_control).<br><br>
+///
+/// A new instance is passed to the callback for each match, and by default
+/// the scan continues from the byte after the match.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanControl {
+    stopped: bool,
+    skip: usize,
+    thread: usize,
+}
+
+impl ScanControl {
+    /// Stops the scan after the callback returns.<br>
+    /// In multi-threaded scans, all the other threads are stopped as well.
+    pub fn stop(&mut self) {
+        self.stopped = true;
+    }
+
+    /// Skips the next `bytes` positions after the current match.<br>
+    /// For instance, skipping the length of the pattern minus one prevents
+    /// the scan from reporting matches that overlap with the current one.<br><br>
+    ///
+    /// Calling this function multiple times accumulates the skipped positions.<br><br>
+    ///
+    /// # Arguments
+    /// * `bytes` - The number of positions to skip.
+    pub fn skip_ahead(&mut self, bytes: usize) {
+        self.skip = self.skip.saturating_add(bytes);
+    }
+}
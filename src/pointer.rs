@@ -0,0 +1,64 @@
+use std::ops::Range;
+
+use crate::{Endian, PatternBuilder};
+
+/// Finds the 32-bit pointers to the given address in the given slice.<br><br>
+///
+/// This is a shortcut for scanning a pattern built with
+/// [`PatternBuilder::from_u32`](struct.PatternBuilder.html#method.from_u32).<br><br>
+///
+/// # Arguments
+/// * `data` - The data slice to scan.
+/// * `target` - The address the pointers point to.
+/// * `endian` - The byte order of the pointers.
+/// * `callback` - The callback to execute when a pointer is found.
+///    - The callback receives the offset of the pointer as an argument.
+///    - It should return `true` to continue scanning, or `false` to stop.
+///
+/// # Returns
+/// True if at least one pointer was found, otherwise false.
+pub fn scan_ptr32(
+    data: &[u8],
+    target: u32,
+    endian: Endian,
+    callback: impl FnMut(usize) -> bool + Send + Sync,
+) -> bool {
+    PatternBuilder::from_u32(target, endian)
+        .build()
+        .scan(data, callback)
+}
+
+/// Finds the 32-bit pointers into the given address range in the given slice,
+/// such as the pointers into the image of a module.<br><br>
+///
+/// # Arguments
+/// * `data` - The data slice to scan.
+/// * `range` - The range of addresses the pointers point into.
+/// * `endian` - The byte order of the pointers.
+/// * `align` - The alignment of the offsets of the pointers in the data. (`0` and `1` check every offset)
+/// * `callback` - The callback to execute when a pointer is found.
+///    - The callback receives the offset and the value of the pointer as arguments.
+///    - It should return `true` to continue scanning, or `false` to stop.
+///
+/// # Returns
+/// True if at least one pointer was found, otherwise false.
+pub fn scan_ptr_range32(
+    data: &[u8],
+    range: Range<u32>,
+    endian: Endian,
+    align: usize,
+    mut callback: impl FnMut(usize, u32) -> bool,
+) -> bool {
+    let mut found = false;
+    for (offset, window) in data.windows(4).enumerate().step_by(align.max(1)) {
+        let value = endian.read_u32([window[0], window[1], window[2], window[3]]);
+        if range.contains(&value) {
+            found = true;
+            if !callback(offset, value) {
+                break;
+            }
+        }
+    }
+
+    found
+}
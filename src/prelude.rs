@@ -0,0 +1,9 @@
+//! Re-exports the types that are needed by most scans.<br><br>
+//!
+//! Importing this module with `use aobscan::prelude::*;` also brings the
+//! [`ObjectScan`](../trait.ObjectScan.html) trait into scope, which is required to
+//! call `scan_object` and the other object scanning methods on a pattern.
+
+pub use crate::{BuilderError, Pattern, PatternBuilder, ScanError};
+#[cfg(feature = "object-scan")]
+pub use crate::{ArchKey, ObjectError, ObjectScan, SectionResult};
@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+use crate::Pattern;
+
+/// A summary of a scan, created by [`Pattern::scan_report`](struct.Pattern.html#method.scan_report).<br><br>
+///
+/// This bundles the information that is commonly reported after a scan, and
+/// its `Display` implementation formats it as a human-readable summary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScanReport {
+    /// The IDA-style representation of the pattern.
+    pub pattern: String,
+    /// The length of the scanned data.
+    pub data_len: usize,
+    /// The number of threads used by the scan.
+    pub threads: usize,
+    /// The offsets of all the matches, sorted in ascending order.
+    pub matches: Vec<usize>,
+    /// The time spent scanning, including the sorting of the matches.
+    pub elapsed: Duration,
+    /// The fraction of the candidate positions of the data that match the pattern.
+    /// (see [`Pattern::density`](struct.Pattern.html#method.density))
+    pub density: f64,
+}
+
+impl std::fmt::Display for ScanReport {
+    /// Formats the report as a summary with a line for each field, in which the
+    /// matches are only counted.<br><br>
+    ///
+    /// # Arguments
+    /// * `f` - The formatter.
+    ///
+    /// # Returns
+    /// Whether the formatting was successful or not.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "pattern: {}", self.pattern)?;
+        writeln!(f, "data:    {} bytes", self.data_len)?;
+        writeln!(f, "threads: {}", self.threads)?;
+        writeln!(f, "matches: {}", self.matches.len())?;
+        writeln!(f, "elapsed: {:?}", self.elapsed)?;
+        write!(f, "density: {:.6}", self.density)
+    }
+}
+
+impl Pattern {
+    /// Performs the AOB scan in the given slice, and summarizes it in a report.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The report of the scan.
+    ///
+    /// # Example
+    /// ```
+    /// let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// let report = pattern.scan_report(b"\x48\x8B\x90\x48\x8B");
+    /// assert_eq!(report.matches, vec![0, 3]);
+    /// println!("{}", report);
+    /// ```
+    pub fn scan_report(&self, data: &[u8]) -> ScanReport {
+        let start = Instant::now();
+        let matches = self.scan_all(data);
+        let elapsed = start.elapsed();
+
+        // The density is computed from the matches, to avoid scanning the data again.
+        let density = match data.len().checked_sub(self.len()) {
+            Some(last) => matches.len() as f64 / (last + 1) as f64,
+            None => 0.0,
+        };
+
+        ScanReport {
+            pattern: self.to_ida_style(),
+            data_len: data.len(),
+            threads: self.threads_for(data.len()),
+            matches,
+            elapsed,
+            density,
+        }
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(target_arch = "x86")]
+use std::arch::x86::{__m128i, _mm_and_si128, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8};
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::{__m128i, _mm_and_si128, _mm_cmpeq_epi8, _mm_loadu_si128, _mm_movemask_epi8};
+
+/// Length of the signatures that can be compared with a single SSE2 comparison.
+pub(crate) const SHORT_LEN: usize = 16;
+
+/// Compares a signature of up to 16 bytes with the data, using a single SSE2 comparison.<br><br>
+///
+/// The signature and the mask are padded with zeros to 16 bytes, so that the lanes
+/// after the end of the signature always match.<br>
+/// If the data is shorter than 16 bytes, it is copied into a zeroed buffer, so that
+/// the comparison never reads past its end.<br><br>
+///
+/// # Arguments
+/// * `data` - The data to compare, which must contain at least the bytes of the signature.
+///   The bytes after the signature are ignored.
+/// * `signature` - The padded signature, with the masked bits already cleared.
+/// * `mask` - The padded bit masks of the signature bytes.
+///
+/// # Returns
+/// True if the data matches the signature, false otherwise.
+pub(crate) fn compare_short(data: &[u8], signature: &[u8; SHORT_LEN], mask: &[u8; SHORT_LEN]) -> bool {
+    let mut tail = [0u8; SHORT_LEN];
+    let data = if data.len() >= SHORT_LEN {
+        &data[..SHORT_LEN]
+    } else {
+        tail[..data.len()].copy_from_slice(data);
+        &tail
+    };
+
+    // SAFETY: SSE2 is enabled for the target, and the three pointers are valid for
+    // reads of 16 bytes, which are unaligned loads.
+    unsafe {
+        let data = _mm_loadu_si128(data.as_ptr() as *const __m128i);
+        let signature = _mm_loadu_si128(signature.as_ptr() as *const __m128i);
+        let mask = _mm_loadu_si128(mask.as_ptr() as *const __m128i);
+
+        let equal = _mm_cmpeq_epi8(_mm_and_si128(data, mask), signature);
+        _mm_movemask_epi8(equal) == 0xFFFF
+    }
+}
@@ -0,0 +1,90 @@
+use crate::Pattern;
+
+/// A receiver for the matches of a scan.<br><br>
+///
+/// This is an alternative to the scan callbacks, that makes it easier to reuse
+/// the logic that collects the matches across different scans.<br>
+/// See [`VecSink`](struct.VecSink.html), [`CountSink`](struct.CountSink.html) and
+/// [`FirstSink`](struct.FirstSink.html) for the built-in implementations.
+pub trait MatchSink {
+    /// Receives a match of the scan.<br><br>
+    ///
+    /// # Arguments
+    /// * `offset` - The offset of the match in the data.
+    ///
+    /// # Returns
+    /// `true` to continue scanning, or `false` to stop.
+    fn accept(&mut self, offset: usize) -> bool;
+}
+
+/// A sink that collects the offsets of all the matches.<br>
+/// In multi-threaded scans, the offsets are collected in no particular order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VecSink {
+    /// The offsets of the matches received so far.
+    pub offsets: Vec<usize>,
+}
+
+impl MatchSink for VecSink {
+    fn accept(&mut self, offset: usize) -> bool {
+        self.offsets.push(offset);
+        true
+    }
+}
+
+/// A sink that counts the matches.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CountSink {
+    /// The number of matches received so far.
+    pub count: usize,
+}
+
+impl MatchSink for CountSink {
+    fn accept(&mut self, _offset: usize) -> bool {
+        self.count += 1;
+        true
+    }
+}
+
+/// A sink that stores the first match it receives, and then stops the scan.<br>
+/// In multi-threaded scans, this is not necessarily the match with the lowest offset.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FirstSink {
+    /// The offset of the first match, if any was received.
+    pub offset: Option<usize>,
+}
+
+impl MatchSink for FirstSink {
+    fn accept(&mut self, offset: usize) -> bool {
+        self.offset.get_or_insert(offset);
+        false
+    }
+}
+
+impl Pattern {
+    /// Performs the AOB scan in the given slice, and hands the matches to the given sink.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `sink` - The sink that receives the matches.
+    ///    - The scan stops when the sink returns `false`.
+    ///
+    /// # Returns
+    /// True if at least one match was found, otherwise false.
+    ///
+    /// # Example
+    /// ```
+    /// use aobscan::CountSink;
+    ///
+    /// let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// let mut sink = CountSink::default();
+    /// pattern.scan_sink(b"\x48\x8B\x90\x48\x8B", &mut sink);
+    /// assert_eq!(sink.count, 2);
+    /// ```
+    pub fn scan_sink(&self, data: &[u8], sink: &mut (impl MatchSink + Send + Sync)) -> bool {
+        self.scan(data, |offset| sink.accept(offset))
+    }
+}
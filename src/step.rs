@@ -0,0 +1,81 @@
+use crate::Pattern;
+
+/// A scanner that finds the matches of a pattern one at a time, under the control
+/// of the caller.<br><br>
+///
+/// Each call to [`step`](#method.step) scans the data from the current position of
+/// the cursor to the next match, and moves the cursor past it, so that the scan can
+/// be paused and inspected between the matches.<br>
+/// The scan is always single-threaded, and the matches are found in ascending order.<br><br>
+///
+/// # Example
+/// ```
+/// let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+///     .unwrap()
+///     .build();
+///
+/// let data = b"\x48\x8B\x90\x48\x8B";
+/// let mut scanner = aobscan::StepScanner::new(&pattern, data);
+///
+/// assert_eq!(scanner.step(), Some(0));
+/// assert_eq!(scanner.position(), 1);
+/// assert_eq!(scanner.step(), Some(3));
+/// assert_eq!(scanner.step(), None);
+/// assert_eq!(scanner.position(), data.len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct StepScanner<'a> {
+    pattern: &'a Pattern,
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> StepScanner<'a> {
+    /// Creates a step scanner for the given pattern and data.<br><br>
+    ///
+    /// # Arguments
+    /// * `pattern` - The pattern to scan for.
+    /// * `data` - The data slice to scan.
+    ///
+    /// # Returns
+    /// The newly created step scanner, with the cursor at the start of the data.
+    pub fn new(pattern: &'a Pattern, data: &'a [u8]) -> Self {
+        Self {
+            pattern,
+            data,
+            position: 0,
+        }
+    }
+
+    /// Scans the data from the cursor to the next match.<br><br>
+    ///
+    /// The cursor is moved to the first position where the next match can start, which
+    /// also depends on the non-overlapping and minimum distance options of the pattern.<br>
+    /// If there are no more matches, the cursor is moved to the end of the data.<br><br>
+    ///
+    /// # Returns
+    /// The offset of the next match, or `None` if the end of the data was reached.
+    pub fn step(&mut self) -> Option<usize> {
+        match self.pattern.first_after(self.data, self.position) {
+            Some(offset) => {
+                let mut next = offset + 1;
+                if !self.pattern.overlapping {
+                    next = next.max(offset + self.pattern.len());
+                }
+
+                self.position = next.max(offset.saturating_add(self.pattern.min_distance));
+                Some(offset)
+            }
+            None => {
+                self.position = self.data.len();
+                None
+            }
+        }
+    }
+
+    /// # Returns
+    /// The offset from which the next step scans the data.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
@@ -0,0 +1,91 @@
+use crate::Pattern;
+
+/// A stateful scanner for data that is received in chunks over time.<br><br>
+///
+/// The caller feeds the chunks of the stream with [`push`](#method.push), and the
+/// matches are reported with their absolute offset in the stream, including the ones
+/// that span multiple chunks.<br>
+/// To do so, the scanner buffers the last `pattern.len() - 1` bytes of the stream,
+/// which is the only memory it retains between the chunks.<br><br>
+///
+/// The exclusions of the pattern are only checked within the buffered data, and the
+/// non-overlapping and minimum distance options only apply within each chunk.<br><br>
+///
+/// # Example
+/// ```
+/// let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+///     .unwrap()
+///     .build();
+///
+/// let mut offsets = vec![];
+/// let mut scanner = aobscan::StreamScanner::new(&pattern);
+/// scanner.push(b"\x90\x90\x48", |offset| offsets.push(offset));
+/// scanner.push(b"\x8B\x05\x90", |offset| offsets.push(offset));
+/// scanner.finish();
+///
+/// assert_eq!(offsets, vec![2]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StreamScanner<'a> {
+    pattern: &'a Pattern,
+    buffer: Vec<u8>,
+    offset: u64,
+}
+
+impl<'a> StreamScanner<'a> {
+    /// Creates a stream scanner for the given pattern.<br><br>
+    ///
+    /// # Arguments
+    /// * `pattern` - The pattern to scan for.
+    ///
+    /// # Returns
+    /// The newly created stream scanner, positioned at the start of the stream.
+    pub fn new(pattern: &'a Pattern) -> Self {
+        Self {
+            pattern,
+            buffer: vec![],
+            offset: 0,
+        }
+    }
+
+    /// Feeds the next chunk of the stream to the scanner.<br><br>
+    ///
+    /// Each match is reported as soon as all of its bytes have been received.<br><br>
+    ///
+    /// # Arguments
+    /// * `chunk` - The next chunk of the stream.
+    /// * `callback` - The callback to execute when a match is found.
+    ///    - The callback receives the offset of the match in the stream as an argument.
+    pub fn push(&mut self, chunk: &[u8], mut callback: impl FnMut(u64) + Send + Sync) {
+        // The buffer contains the tail of the previous chunks, followed by the new one.
+        // The tail is shorter than the pattern, so the matches found in the buffer
+        // could not have been found in the previous chunks, unless they omit optional
+        // bytes and end within the tail.
+        let tail_len = self.buffer.len();
+        self.buffer.extend_from_slice(chunk);
+
+        let base = self.offset;
+        let (pattern, buffer) = (self.pattern, &self.buffer);
+        pattern.scan(buffer, |offset| {
+            if !pattern.matches_at(&buffer[..tail_len], offset) {
+                callback(base + offset as u64);
+            }
+            true
+        });
+
+        // Only keep the bytes that can be part of a match with the next chunks.
+        let keep = self.pattern.len().saturating_sub(1).min(self.buffer.len());
+        let discarded = self.buffer.len() - keep;
+        self.buffer.drain(..discarded);
+        self.offset += discarded as u64;
+    }
+
+    /// Ends the stream, and resets the scanner so that it can be used for a new stream.<br><br>
+    ///
+    /// As the matches are reported as soon as all of their bytes are received, there
+    /// are never pending matches, so the buffered tail of the stream is discarded.
+    pub fn finish(&mut self) {
+        self.buffer.clear();
+        self.offset = 0;
+    }
+}
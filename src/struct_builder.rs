@@ -0,0 +1,114 @@
+use crate::{BuilderError, Pattern};
+
+/// Builder for patterns that describe a struct layout, made of named fields.<br><br>
+///
+/// Each field is either a sequence of fixed bytes or a number of wildcard bytes,
+/// and the offset of each field in the pattern is recorded, so that the fields
+/// can be located in the matches with [`Pattern::field_offset`](struct.Pattern.html#method.field_offset).<br><br>
+///
+/// # Example
+/// ```
+/// let data = b"\x90\x48\x8B\x05\x10\x20\x30\x40\x90";
+/// let pattern = aobscan::StructPatternBuilder::new()
+///     .field("opcode", &[0x48, 0x8B, 0x05])
+///     .wildcard("disp", 4)
+///     .field("suffix", &[0x90])
+///     .build()
+///     .unwrap();
+///
+/// pattern.scan(data, |offset| {
+///     let disp = offset + pattern.field_offset("disp").unwrap();
+///     assert_eq!(&data[disp..disp + 4], b"\x10\x20\x30\x40");
+///     true
+/// });
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StructPatternBuilder {
+    signature: Vec<u8>,
+    mask: Vec<bool>,
+    fields: Vec<(String, usize)>,
+}
+
+impl StructPatternBuilder {
+    /// Creates an empty struct pattern builder.<br><br>
+    ///
+    /// # Returns
+    /// The newly created builder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a field made of fixed bytes to the pattern.<br><br>
+    ///
+    /// # Arguments
+    /// * `name` - The name of the field.
+    /// * `bytes` - The bytes of the field.
+    ///
+    /// # Returns
+    /// The current instance of the builder.
+    pub fn field(mut self, name: &str, bytes: &[u8]) -> Self {
+        self.fields.push((name.to_string(), self.signature.len()));
+        self.signature.extend_from_slice(bytes);
+        self.mask.extend(std::iter::repeat_n(true, bytes.len()));
+        self
+    }
+
+    /// Appends a field made of wildcard bytes to the pattern.<br><br>
+    ///
+    /// # Arguments
+    /// * `name` - The name of the field.
+    /// * `size` - The size of the field in bytes.
+    ///
+    /// # Returns
+    /// The current instance of the builder.
+    pub fn wildcard(mut self, name: &str, size: usize) -> Self {
+        self.fields.push((name.to_string(), self.signature.len()));
+        self.signature.extend(std::iter::repeat_n(0, size));
+        self.mask.extend(std::iter::repeat_n(false, size));
+        self
+    }
+
+    /// Builds a new pattern instance with the specified fields.<br><br>
+    ///
+    /// # Returns
+    /// The created pattern instance, or an error if the fields are invalid.<br><br>
+    ///
+    /// # Errors
+    /// * `BuilderError::InvalidSignature` - The pattern is empty or contains duplicate field names.
+    pub fn build(self) -> Result<Pattern, BuilderError> {
+        if self.signature.is_empty() {
+            Err(BuilderError::InvalidSignature(
+                "the pattern cannot be empty".to_string()
+            ))?
+        }
+
+        for (i, (name, _)) in self.fields.iter().enumerate() {
+            if self.fields[..i].iter().any(|(other, _)| other == name) {
+                Err(BuilderError::InvalidSignature(
+                    format!("the pattern contains the field '{}' more than once", name)
+                ))?
+            }
+        }
+
+        let mut pattern = Pattern::new(self.signature, self.mask, 1);
+        pattern.fields = self.fields;
+        Ok(pattern)
+    }
+}
+
+impl Pattern {
+    /// Finds the offset of a named field in the pattern.
+    /// (see [`StructPatternBuilder`](struct.StructPatternBuilder.html))<br><br>
+    ///
+    /// # Arguments
+    /// * `name` - The name of the field.
+    ///
+    /// # Returns
+    /// The offset of the field from the start of a match, or `None` if the
+    /// pattern does not contain a field with the given name.
+    pub fn field_offset(&self, name: &str) -> Option<usize> {
+        self.fields.iter()
+            .find(|(field, _)| field == name)
+            .map(|&(_, offset)| offset)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::Pattern;
+
+impl Pattern {
+    /// Performs the AOB scan in the given slice, and asserts the number of matches.<br><br>
+    ///
+    /// This is meant to be used in tests, as the panic message contains the
+    /// pattern and the actual number of matches.<br><br>
+    ///
+    /// # Arguments
+    /// * `data` - The data slice to scan.
+    /// * `expected` - The expected number of matches.
+    ///
+    /// # Panics
+    /// If the number of matches is different from `expected`.
+    ///
+    /// # Example
+    /// ```
+    /// let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+    ///     .unwrap()
+    ///     .build();
+    ///
+    /// pattern.assert_count(b"\x48\x8B\x90\x48\x8B", 2);
+    /// ```
+    #[track_caller]
+    pub fn assert_count(&self, data: &[u8], expected: usize) {
+        let mut count = 0usize;
+        self.scan(data, |_| {
+            count += 1;
+            true
+        });
+
+        if count != expected {
+            panic!(
+                "pattern `{}` matched {} times in {} bytes, expected {}",
+                self.to_ida_style(),
+                count,
+                data.len(),
+                expected,
+            );
+        }
+    }
+}
@@ -0,0 +1,44 @@
+use crate::Pattern;
+
+/// Performs the AOB scan of multiple patterns in the given slice, and merges
+/// their matches in a single timeline.<br><br>
+///
+/// This is useful to report the results of a set of signatures in the order in
+/// which they appear in the data.<br><br>
+///
+/// # Arguments
+/// * `patterns` - The patterns to scan for, each with the label that identifies it.
+/// * `data` - The data slice to scan.
+///
+/// # Returns
+/// The offsets of the matches of all the patterns, each with the label of its pattern.<br>
+/// The matches are sorted by offset, and the matches of different patterns at the same
+/// offset are in the order of the patterns.
+///
+/// # Example
+/// ```
+/// let patterns = vec![
+///     ("mov".to_string(), aobscan::PatternBuilder::from_ida_style("48 8B").unwrap().build()),
+///     ("nop".to_string(), aobscan::PatternBuilder::from_ida_style("90").unwrap().build()),
+/// ];
+///
+/// let timeline = aobscan::scan_timeline(&patterns, b"\x90\x48\x8B\x90");
+/// assert_eq!(timeline, vec![
+///     (0, "nop".to_string()),
+///     (1, "mov".to_string()),
+///     (3, "nop".to_string()),
+/// ]);
+/// ```
+pub fn scan_timeline(patterns: &[(String, Pattern)], data: &[u8]) -> Vec<(usize, String)> {
+    let mut timeline = patterns.iter()
+        .flat_map(|(label, pattern)| {
+            pattern.scan_all(data)
+                .into_iter()
+                .map(move |offset| (offset, label.clone()))
+        })
+        .collect::<Vec<_>>();
+
+    // The sort is stable, so the matches at the same offset keep the order of the patterns.
+    timeline.sort_by_key(|(offset, _)| *offset);
+    timeline
+}
@@ -0,0 +1,59 @@
+#[test]
+/// Tests that a rare anchor byte has a low selectivity, and a common one a high selectivity.
+fn anchor_selectivity_rare_and_common() {
+    // The data is made of 0x90 bytes, with a few 0x48 bytes.
+    let mut data = vec![0x90u8; 0x1000];
+    for offset in (0..data.len()).step_by(0x100) {
+        data[offset] = 0x48;
+    }
+
+    let rare = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+    let common = aobscan::PatternBuilder::from_ida_style("90 ? 90")
+        .unwrap()
+        .build();
+
+    let rare_selectivity = rare.anchor_selectivity(&data);
+    let common_selectivity = common.anchor_selectivity(&data);
+
+    assert!(rare_selectivity < 0.01);
+    assert!(common_selectivity > 0.99);
+}
+
+#[test]
+/// Tests the selectivity of patterns without an anchor, and of data shorter than the pattern.
+fn anchor_selectivity_edge_cases() {
+    let wildcards = aobscan::PatternBuilder::from_ida_style("? ?")
+        .unwrap()
+        .build();
+    assert_eq!(wildcards.anchor_selectivity(&[0u8; 0x10]), 1.0);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("? 48 8B")
+        .unwrap()
+        .build();
+    assert_eq!(pattern.anchor_selectivity(&[0x48, 0x48]), 0.0);
+    // Only the positions after the leading wildcard are candidates.
+    assert_eq!(pattern.anchor_selectivity(&[0x48, 0x48, 0x8B, 0x00]), 0.5);
+}
+
+#[test]
+/// Tests that the selectivity of the patterns searched by their longest fixed fragment
+/// is the fraction of the positions where the whole fragment matches.
+fn anchor_selectivity_fragment() {
+    let data = [0x90, 0x90, 0x90, 0xCC].repeat(0x100);
+    let build = |pattern: &str, dual_anchor| {
+        aobscan::PatternBuilder::from_ida_style(pattern)
+            .unwrap()
+            .with_dual_anchor(dual_anchor)
+            .build()
+    };
+
+    // The first byte is common, but the whole fragment is never found.
+    assert_eq!(build("? ? ? 90 90 90 90 ? ?", true).anchor_selectivity(&data), 0.0);
+    assert!(build("? ? ? 90 90 90 90 ? ?", false).anchor_selectivity(&data) > 0.7);
+
+    // The overlapping occurrences of the fragment are all counted.
+    assert_eq!(build("90 90", true).anchor_selectivity(&[0x90; 5]), 1.0);
+    assert_eq!(build("90 90 ? CC", true).anchor_selectivity(&data[..8]), 0.6);
+}
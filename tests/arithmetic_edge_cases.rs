@@ -0,0 +1,100 @@
+//! Adversarial inputs for the computations that could overflow or underflow.
+//! These tests are meaningful in debug mode, where arithmetic overflows panic.
+
+#[test]
+/// Tests that patterns created with mismatched or empty arguments never panic.
+fn mismatched_constructor_arguments() {
+    let data = [0x48u8, 0x8B, 0x05, 0x48, 0x8B];
+
+    // The longer argument is truncated.
+    let pattern = aobscan::Pattern::new(vec![0x48, 0x8B, 0x05], vec![true, true], 1);
+    assert_eq!(pattern.to_ida_style(), "48 8B");
+    assert_eq!(pattern.scan_all(&data), vec![0, 3]);
+
+    let pattern = aobscan::Pattern::new(vec![0x48], vec![true, true, true], 1);
+    assert_eq!(pattern.to_ida_style(), "48");
+
+    // Empty patterns have nothing to compare, so they never match.
+    for threads in [0, 1, 4] {
+        let pattern = aobscan::Pattern::new(vec![], vec![], threads);
+        assert!(pattern.is_empty());
+        assert!(pattern.scan_all(&data).is_empty());
+        assert!(!pattern.matches_at(&data, 0));
+    }
+}
+
+#[test]
+/// Tests multi-threaded scans of data shorter than the pattern and than the thread count.
+fn data_shorter_than_chunks() {
+    for threads in 2..=8 {
+        let pattern = aobscan::Pattern::new(
+            vec![0x00, 0x00, 0x00, 0x48, 0x8B],
+            vec![false, false, false, true, true],
+            threads,
+        );
+
+        for len in 0..8 {
+            let mut data = vec![0x48u8; len];
+            if len >= 5 {
+                data[len - 1] = 0x8B;
+                assert_eq!(pattern.scan_all(&data), vec![len - 5]);
+            } else {
+                assert!(pattern.scan_all(&data).is_empty());
+            }
+        }
+    }
+}
+
+#[test]
+/// Tests the options that offset the scan cursor with extreme values.
+fn extreme_scan_options() {
+    let data = [0xAAu8; 0x20];
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("? ? AA")
+        .unwrap()
+        .with_min_distance(usize::MAX)
+        .build();
+    assert_eq!(pattern.scan_all(&data), vec![0]);
+
+    let mut offsets = vec![];
+    pattern.scan_control(&data, |offset, control| {
+        offsets.push(offset);
+        control.skip_ahead(usize::MAX);
+    });
+    assert_eq!(offsets, vec![0]);
+
+    assert_eq!(pattern.scan_page(&data, usize::MAX, usize::MAX), (vec![], false));
+    assert_eq!(pattern.scan_page(&data, 0, usize::MAX), (vec![0], false));
+    assert_eq!(pattern.carve(&data, usize::MAX), vec![&data[..]]);
+    assert!(!pattern.matches_at(&data, usize::MAX - 1));
+}
+
+#[test]
+/// Tests the stitching of buffers that are shorter than the pattern.
+fn iovecs_shorter_than_pattern() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05 90")
+        .unwrap()
+        .build();
+
+    let mut offsets = vec![];
+    pattern.scan_iovecs(&[b"", b"\x48", b"", b"\x8B\x05", b"\x90", b""], |offset| {
+        offsets.push(offset);
+        true
+    });
+    assert_eq!(offsets, vec![0]);
+}
+
+#[test]
+/// Tests the stream scanner with chunks that are shorter than the pattern.
+fn stream_chunks_shorter_than_pattern() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    let mut offsets = vec![];
+    let mut scanner = aobscan::StreamScanner::new(&pattern);
+    for chunk in [&b""[..], b"\x48", b"", b"\x8B", b"\x05"] {
+        scanner.push(chunk, |offset| offsets.push(offset));
+    }
+    assert_eq!(offsets, vec![0]);
+}
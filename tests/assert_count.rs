@@ -0,0 +1,31 @@
+#![cfg(feature = "testing")]
+
+/// Builds a buffer with two matches of `48 8B`.
+fn planted_data() -> Vec<u8> {
+    let mut data = vec![0u8; 0x100];
+    data[0x10..0x12].copy_from_slice(&[0x48, 0x8B]);
+    data[0x80..0x82].copy_from_slice(&[0x48, 0x8B]);
+    data
+}
+
+#[test]
+/// Tests that the assertion passes when the number of matches is the expected one.
+fn assert_count_passes() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build();
+
+    pattern.assert_count(&planted_data(), 2);
+    pattern.assert_count(&[], 0);
+}
+
+#[test]
+#[should_panic(expected = "pattern `48 8B` matched 2 times in 256 bytes, expected 3")]
+/// Tests that the assertion panics with the pattern and the actual number of matches.
+fn assert_count_panics() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build();
+
+    pattern.assert_count(&planted_data(), 3);
+}
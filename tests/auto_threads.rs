@@ -0,0 +1,50 @@
+use std::sync::Mutex;
+
+#[test]
+/// Tests that the buffers smaller than the threshold are scanned by a single thread.
+fn auto_threads_small_buffer() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 05")
+        .unwrap()
+        .with_auto_threads()
+        .build();
+    assert_eq!(pattern.get_threads(), num_cpus::get());
+
+    let mut data = vec![0u8; 0x8000];
+    for offset in (0..data.len()).step_by(0x100) {
+        data[offset..offset + 4].copy_from_slice(&[0x48, 0x8B, 0x00, 0x05]);
+    }
+
+    let hits = Mutex::new(vec![]);
+    pattern.scan_with_thread_id(&data, |offset, thread| {
+        hits.lock().unwrap().push((offset, thread));
+        true
+    });
+
+    let hits = hits.into_inner().unwrap();
+    assert_eq!(hits.len(), 0x80);
+    assert!(hits.iter().all(|&(_, thread)| thread == 0));
+}
+
+#[test]
+/// Tests that the scans of large buffers find all the matches.
+fn auto_threads_large_buffer() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 05")
+        .unwrap()
+        .with_auto_threads()
+        .build();
+
+    let mut data = vec![0u8; 0x100000];
+    for offset in (0..data.len()).step_by(0x1000) {
+        data[offset..offset + 4].copy_from_slice(&[0x48, 0x8B, 0x00, 0x05]);
+    }
+
+    let offsets = Mutex::new(vec![]);
+    pattern.scan(&data, |offset| {
+        offsets.lock().unwrap().push(offset);
+        true
+    });
+
+    let mut offsets = offsets.into_inner().unwrap();
+    offsets.sort_unstable();
+    assert_eq!(offsets, (0..data.len()).step_by(0x1000).collect::<Vec<_>>());
+}
@@ -0,0 +1,33 @@
+#[test]
+/// Tests that a pattern starting with its distinguishing byte compares fewer bytes
+/// than one ending with it.
+fn avg_compare_length_byte_order() {
+    // Zero-filled data, where only the non-zero byte of the patterns distinguishes them.
+    let data = vec![0u8; 0x1000];
+
+    let first = aobscan::PatternBuilder::from_ida_style("CC 00 00 00 00 00 00 00")
+        .unwrap()
+        .build();
+    let last = aobscan::PatternBuilder::from_ida_style("00 00 00 00 00 00 00 CC")
+        .unwrap()
+        .build();
+
+    assert_eq!(first.avg_compare_length(&data), 1.0);
+    assert_eq!(last.avg_compare_length(&data), 8.0);
+}
+
+#[test]
+/// Tests that the matches compare the whole signature, and that the data shorter
+/// than the pattern has no candidate positions.
+fn avg_compare_length_matches() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? 90 ? 90")
+        .unwrap()
+        .build();
+
+    // Every candidate position matches the three bytes after the leading wildcard.
+    assert_eq!(pattern.avg_compare_length(&[0x90; 0x10]), 3.0);
+    // The positions alternate between a mismatch of the first byte and a match.
+    let data = [0x90, 0x00].repeat(0x10);
+    assert!((pattern.avg_compare_length(&data) - 2.0).abs() < 0.1);
+    assert_eq!(pattern.avg_compare_length(&[0x90; 3]), 0.0);
+}
@@ -0,0 +1,21 @@
+#[test]
+/// Tests that the carved regions start at the matches and are clamped to the end of the data.
+fn carve() {
+    let mut data = vec![0u8; 0x100];
+    data[0x10..0x12].copy_from_slice(b"\x4D\x5A");
+    data[0x80..0x82].copy_from_slice(b"\x4D\x5A");
+    data[0xFC..0xFE].copy_from_slice(b"\x4D\x5A");
+
+    let regions = aobscan::PatternBuilder::from_ida_style("4D 5A")
+        .unwrap()
+        .build()
+        .carve(&data, 0x20);
+
+    assert_eq!(regions.len(), 3);
+    assert_eq!(regions[0], &data[0x10..0x30]);
+    assert_eq!(regions[1], &data[0x80..0xA0]);
+
+    // The last region is clamped to the end of the data.
+    assert_eq!(regions[2], &data[0xFC..]);
+    assert_eq!(regions[2].len(), 4);
+}
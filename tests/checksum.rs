@@ -0,0 +1,54 @@
+#![cfg(feature = "checksum")]
+
+/// Builds a buffer with two matches of `48 8B 05`, followed by different displacements.
+fn planted_data() -> Vec<u8> {
+    let mut data = vec![0u8; 0x40];
+    data[0x10..0x17].copy_from_slice(&[0x48, 0x8B, 0x05, 0x11, 0x22, 0x33, 0x44]);
+    data[0x20..0x27].copy_from_slice(&[0x48, 0x8B, 0x05, 0x55, 0x66, 0x77, 0x88]);
+    data
+}
+
+#[test]
+/// Tests that only the matches whose bytes have the expected checksum are accepted.
+fn checksum_accept_and_reject() {
+    let data = planted_data();
+    let expected = crc32fast::hash(&[0x48, 0x8B, 0x05, 0x11, 0x22, 0x33, 0x44]);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .with_checksum(0..7, expected)
+        .build();
+    assert_eq!(pattern.scan_all(&data), vec![0x10]);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .with_checksum(3..7, crc32fast::hash(&[0x55, 0x66, 0x77, 0x88]))
+        .build();
+    assert_eq!(pattern.scan_all(&data), vec![0x20]);
+
+    // Both checksums must match.
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .with_checksum(0..7, expected)
+        .with_checksum(3..7, crc32fast::hash(&[0x55, 0x66, 0x77, 0x88]))
+        .build();
+    assert!(pattern.scan_all(&data).is_empty());
+}
+
+#[test]
+/// Tests that the checksums of ranges past the end of the data reject the match.
+fn checksum_out_of_bounds() {
+    let data = [0x48, 0x8B, 0x05, 0x11];
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .with_checksum(0..4, crc32fast::hash(&data))
+        .build();
+    assert_eq!(pattern.scan_all(&data), vec![0]);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .with_checksum(0..5, crc32fast::hash(&data))
+        .build();
+    assert!(pattern.scan_all(&data).is_empty());
+}
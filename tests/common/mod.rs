@@ -1,4 +1,9 @@
-use rand::RngCore;
+// Not every test uses all the helpers.
+#![allow(dead_code)]
+
+use object::{Architecture, BinaryFormat, Endianness, SectionKind};
+use rand::{RngCore, SeedableRng};
+use rand::rngs::StdRng;
 
 //noinspection ALL
 pub fn random_bytes(len: usize) -> Vec<u8> {
@@ -7,3 +12,93 @@ pub fn random_bytes(len: usize) -> Vec<u8> {
     rng.fill_bytes(&mut bytes);
     bytes
 }
+
+/// Generates random bytes from a fixed seed, so that the failures of the tests
+/// that use them can be reproduced.
+pub fn random_bytes_seeded(len: usize, seed: u64) -> Vec<u8> {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut bytes = vec![0u8; len];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+/// Builds an x86-64 relocatable object file containing the given sections.<br>
+/// The sections are laid out contiguously in the file, in the given order.<br><br>
+///
+/// In Mach-O files, text sections are placed in the `__TEXT` segment,
+/// and all the other sections in the `__DATA` segment.
+pub fn build_object(format: BinaryFormat, sections: &[(&str, SectionKind, &[u8])]) -> Vec<u8> {
+    build_object_for(format, Architecture::X86_64, sections)
+}
+
+/// Builds a little-endian relocatable object file for the given architecture,
+/// containing the given sections. (see [`build_object`])
+pub fn build_object_for(
+    format: BinaryFormat,
+    architecture: Architecture,
+    sections: &[(&str, SectionKind, &[u8])],
+) -> Vec<u8> {
+    let mut object = object::write::Object::new(format, architecture, Endianness::Little);
+
+    for (name, kind, data) in sections {
+        let segment = match (format, kind) {
+            (BinaryFormat::MachO, SectionKind::Text) => b"__TEXT".to_vec(),
+            (BinaryFormat::MachO, _) => b"__DATA".to_vec(),
+            _ => vec![],
+        };
+
+        let section = object.add_section(segment, name.as_bytes().to_vec(), *kind);
+        object.set_section_data(section, data.to_vec(), 1);
+    }
+
+    object.write().unwrap()
+}
+
+/// Builds a Mach-O FAT archive containing the given THIN binaries.<br>
+/// Each slice is a pair of CPU type (e.g. `object::macho::CPU_TYPE_X86_64`) and
+/// file data, and is aligned to a 4 KiB boundary.
+pub fn build_fat(slices: &[(u32, &[u8])]) -> Vec<u8> {
+    const ALIGN: u32 = 12;
+
+    let mut header = Vec::new();
+    header.extend(object::macho::FAT_MAGIC.to_be_bytes());
+    header.extend((slices.len() as u32).to_be_bytes());
+
+    let mut body = Vec::new();
+    let mut offset = 1usize << ALIGN;
+    for (cpu_type, data) in slices {
+        header.extend(cpu_type.to_be_bytes());
+        header.extend(0u32.to_be_bytes());
+        header.extend((offset as u32).to_be_bytes());
+        header.extend((data.len() as u32).to_be_bytes());
+        header.extend(ALIGN.to_be_bytes());
+
+        body.push((offset, *data));
+        offset = (offset + data.len()).next_multiple_of(1 << ALIGN);
+    }
+
+    let mut fat = header;
+    for (offset, data) in body {
+        fat.resize(offset, 0);
+        fat.extend_from_slice(data);
+    }
+
+    fat
+}
+
+/// Brute-force reference scanner, used to validate the results of the library.<br>
+/// The mask contains `false` for the wildcard bytes.
+pub fn naive_scan(data: &[u8], signature: &[u8], mask: &[bool]) -> Vec<usize> {
+    if data.len() < signature.len() {
+        return vec![];
+    }
+
+    (0..=data.len() - signature.len())
+        .filter(|&offset| {
+            signature.iter()
+                .zip(mask)
+                .enumerate()
+                .all(|(i, (&byte, &fixed))| !fixed || data[offset + i] == byte)
+        })
+        .collect()
+}
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+#[test]
+/// Tests that the same pattern string returns the same shared pattern.
+fn compile_cached_shared() {
+    let first = aobscan::compile_cached("48 8B ? ? 05").unwrap();
+    let second = aobscan::compile_cached("48 8B ? ? 05").unwrap();
+    let other = aobscan::compile_cached("48 8B ? ? 06").unwrap();
+
+    assert!(Arc::ptr_eq(&first, &second));
+    assert!(!Arc::ptr_eq(&first, &other));
+    assert_eq!(*first, aobscan::PatternBuilder::from_ida_style("48 8B ? ? 05").unwrap().build());
+}
+
+#[test]
+/// Tests that invalid patterns return the parse error.
+fn compile_cached_invalid() {
+    assert!(aobscan::compile_cached("48 8B ZZ").is_err());
+    assert!(aobscan::compile_cached("").is_err());
+}
+
+#[test]
+/// Tests that the cache keeps working when it is full, and can be used from multiple threads.
+fn compile_cached_bounded() {
+    std::thread::scope(|scope| {
+        for thread in 0..4 {
+            scope.spawn(move || {
+                for i in 0..0x200 {
+                    let pattern = format!("{:02X} {:02X} ? {:02X}", thread, i & 0xFF, i >> 8);
+                    let compiled = aobscan::compile_cached(&pattern).unwrap();
+                    assert_eq!(compiled.to_ida_style(), pattern);
+                }
+            });
+        }
+    });
+
+    // Recently used patterns are still cached.
+    let first = aobscan::compile_cached("90 90 90").unwrap();
+    let second = aobscan::compile_cached("90 90 90").unwrap();
+    assert!(Arc::ptr_eq(&first, &second));
+}
@@ -0,0 +1,30 @@
+#[test]
+/// Tests that a unique, fully fixed pattern scores higher than an ambiguous
+/// pattern with wildcards.
+fn confidence_ranking() {
+    let mut data = vec![0u8; 0x1000];
+    data[0x100..0x106].copy_from_slice(&[0x48, 0x8B, 0x05, 0x11, 0x22, 0x33]);
+    data[0x200..0x206].copy_from_slice(&[0x48, 0x8B, 0x05, 0x44, 0x55, 0x66]);
+    data[0x300..0x306].copy_from_slice(&[0x48, 0x8B, 0x05, 0x77, 0x88, 0x99]);
+
+    let unique = aobscan::PatternBuilder::from_ida_style("48 8B 05 44 55 66")
+        .unwrap()
+        .build();
+    let ambiguous = aobscan::PatternBuilder::from_ida_style("48 ? 05 ? ? ?")
+        .unwrap()
+        .build();
+
+    assert_eq!(unique.confidence(&data), 1.0);
+    assert_eq!(ambiguous.confidence(&data), (2.0 / 3.0) / 3.0);
+    assert!(unique.confidence(&data) > ambiguous.confidence(&data));
+}
+
+#[test]
+/// Tests that a pattern without matches has no confidence.
+fn confidence_no_matches() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    assert_eq!(pattern.confidence(&[0u8; 0x100]), 0.0);
+}
@@ -0,0 +1,47 @@
+use aobscan::PatternStyle;
+
+#[test]
+/// Tests the conversion of IDA-style patterns to hex strings and back.
+fn convert_ida_hex() {
+    assert_eq!(
+        aobscan::convert("48 8B 05 ? ? ? ? 90", PatternStyle::Ida, PatternStyle::Hex).unwrap(),
+        "488b05????????90"
+    );
+
+    assert_eq!(
+        aobscan::convert("488b05????????90", PatternStyle::Hex, PatternStyle::Ida).unwrap(),
+        "48 8B 05 ? ? ? ? 90"
+    );
+
+    assert!(aobscan::convert("48 8B ZZ", PatternStyle::Ida, PatternStyle::Hex).is_err());
+}
+
+#[test]
+/// Tests the conversion from and to code-style patterns.
+fn convert_code() {
+    assert_eq!(
+        aobscan::convert("? 48 8B ? 90", PatternStyle::Ida, PatternStyle::Code).unwrap(),
+        r"\x00\x48\x8B\x00\x90 ?..?."
+    );
+
+    assert_eq!(
+        aobscan::convert(r"\x48\x8b\x00\x90 ..?.", PatternStyle::Code, PatternStyle::Ida).unwrap(),
+        "48 8B ? 90"
+    );
+
+    assert!(aobscan::convert(r"\x48\x8B", PatternStyle::Code, PatternStyle::Ida).is_err());
+    assert!(aobscan::convert(r"\x48\x8 ..", PatternStyle::Code, PatternStyle::Ida).is_err());
+    assert!(aobscan::convert(r"\x48\x8B ...", PatternStyle::Code, PatternStyle::Ida).is_err());
+}
+
+#[test]
+/// Tests that converting a pattern through all the styles gives back the original pattern.
+fn convert_round_trip() {
+    let original = "? ? 48 8B 05 ? ? ? ? 48 8B 88";
+
+    let hex = aobscan::convert(original, PatternStyle::Ida, PatternStyle::Hex).unwrap();
+    let code = aobscan::convert(&hex, PatternStyle::Hex, PatternStyle::Code).unwrap();
+    let ida = aobscan::convert(&code, PatternStyle::Code, PatternStyle::Ida).unwrap();
+
+    assert_eq!(ida, original);
+}
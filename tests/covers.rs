@@ -0,0 +1,36 @@
+#[test]
+/// Tests whether the bytes inside and outside of the matches are covered.
+fn covers_bytes() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 ? 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x40];
+    data[0x10..0x13].copy_from_slice(&[0x48, 0x8B, 0x05]);
+
+    // Every byte of the span is covered, including the wildcards.
+    assert!(pattern.covers(&data, 0x10));
+    assert!(pattern.covers(&data, 0x11));
+    assert!(pattern.covers(&data, 0x12));
+
+    assert!(!pattern.covers(&data, 0x0F));
+    assert!(!pattern.covers(&data, 0x13));
+    assert!(!pattern.covers(&data, usize::MAX));
+    assert_eq!(pattern.matches_covering(&data, 0x13), Vec::<usize>::new());
+}
+
+#[test]
+/// Tests that the bytes inside overlapping matches are covered by all of them.
+fn covers_overlapping_matches() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("AA AA AA")
+        .unwrap()
+        .build();
+
+    let data = [0x00, 0xAA, 0xAA, 0xAA, 0xAA, 0x00];
+    assert_eq!(pattern.matches_covering(&data, 0), Vec::<usize>::new());
+    assert_eq!(pattern.matches_covering(&data, 1), vec![1]);
+    assert_eq!(pattern.matches_covering(&data, 2), vec![1, 2]);
+    assert_eq!(pattern.matches_covering(&data, 3), vec![1, 2]);
+    assert_eq!(pattern.matches_covering(&data, 4), vec![2]);
+    assert!(pattern.covers(&data, 3));
+}
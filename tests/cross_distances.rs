@@ -0,0 +1,38 @@
+#[test]
+/// Tests that a field located at a fixed distance from a marker produces a consistent distance.
+fn cross_distances_fixed_delta() {
+    // Each record starts with a marker, and has a field 0x20 bytes after it.
+    let mut data = vec![0u8; 0x400];
+    for record in (0..data.len()).step_by(0x80) {
+        data[record..record + 2].copy_from_slice(&[0xDE, 0xAD]);
+        data[record + 0x20..record + 0x22].copy_from_slice(&[0xBE, 0xEF]);
+    }
+
+    let marker = aobscan::PatternBuilder::from_ida_style("DE AD")
+        .unwrap()
+        .build();
+    let field = aobscan::PatternBuilder::from_ida_style("BE EF")
+        .unwrap()
+        .build();
+
+    // Only the field of the same record is close enough to each marker.
+    assert_eq!(marker.cross_distances(&field, &data, 0x40), vec![0x20; 8]);
+
+    // With a larger range, the fields of the neighbouring records are paired as well.
+    let distances = marker.cross_distances(&field, &data, 0x80);
+    assert_eq!(&distances[..4], &[0x20, -0x60, 0x20, -0x60]);
+    assert_eq!(distances.iter().filter(|&&distance| distance == 0x20).count(), 8);
+
+    // The distances are signed.
+    assert_eq!(field.cross_distances(&marker, &data, 0x40), vec![-0x20; 8]);
+}
+
+#[test]
+/// Tests that patterns without matches produce no distances.
+fn cross_distances_no_matches() {
+    let a = aobscan::PatternBuilder::from_ida_style("DE AD").unwrap().build();
+    let b = aobscan::PatternBuilder::from_ida_style("BE EF").unwrap().build();
+
+    assert!(a.cross_distances(&b, &[0xDE, 0xAD, 0x00], usize::MAX).is_empty());
+    assert_eq!(a.cross_distances(&a, &[0xDE, 0xAD, 0x00], 0), vec![0]);
+}
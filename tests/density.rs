@@ -0,0 +1,32 @@
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that a specific pattern has a low density, and a pattern made of wildcards a full density.
+fn density_specific_and_wildcards() {
+    let data = random_bytes(0x10000);
+
+    let specific = aobscan::PatternBuilder::from_ida_style("48 8B 05 90")
+        .unwrap()
+        .build();
+    let wildcards = aobscan::PatternBuilder::from_ida_style("? ? ?")
+        .unwrap()
+        .build();
+
+    assert!(specific.density(&data) < 0.001);
+    assert_eq!(wildcards.density(&data), 1.0);
+}
+
+#[test]
+/// Tests the density of data that is shorter than or as long as the pattern.
+fn density_short_data() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? 48 8B")
+        .unwrap()
+        .build();
+
+    assert_eq!(pattern.density(&[]), 0.0);
+    assert_eq!(pattern.density(&[0x90, 0x48]), 0.0);
+    assert_eq!(pattern.density(&[0x90, 0x48, 0x8B]), 1.0);
+    assert_eq!(pattern.density(&[0x90, 0x48, 0x8B, 0x8B]), 0.5);
+}
@@ -0,0 +1,63 @@
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Wildcard-heavy patterns, with the fixed bytes deep inside the signature.
+const SPARSE_PATTERNS: [&str; 4] = [
+    "48 ? ? ? ? ? ? ? ? ? ? ? ? 8B",
+    "? ? ? ? ? ? ? ? 00 ? ? ? ? ? ? E8",
+    "? ? ? 5A ? ? ? ? ? ? ? ? ? ? ? ? ? ? ? ? ? ? ? ? ? 5A",
+    "? ? ? ? ? ? ? ? ? ? ? ? ? ? ? 7F",
+];
+
+/// Builds the pattern with the given anchoring mode.
+fn build(pattern: &str, dual_anchor: bool) -> aobscan::Pattern {
+    aobscan::PatternBuilder::from_ida_style(pattern)
+        .unwrap()
+        .with_dual_anchor(dual_anchor)
+        .build()
+}
+
+#[test]
+/// Tests that dual anchors report the same matches as the single-anchor path on random data.
+fn dual_anchor_same_matches() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x940);
+    let mut data = vec![0u8; 0x10000];
+    rng.fill_bytes(&mut data);
+    // Skew the data towards a few values, so that the patterns match more often.
+    for byte in data.iter_mut() {
+        if rng.gen_bool(0.5) {
+            *byte = [0x00, 0x48, 0x5A, 0x8B, 0xE8][rng.gen_range(0..5)];
+        }
+    }
+
+    for pattern in SPARSE_PATTERNS {
+        let expected = build(pattern, false).scan_all(&data);
+        assert!(!expected.is_empty());
+        assert_eq!(build(pattern, true).scan_all(&data), expected);
+    }
+}
+
+#[test]
+/// Tests that dual anchors respect the partially fixed bytes and the matching options.
+fn dual_anchor_options() {
+    let data = b"key=deadbeef;KEY=DEADBEEF;mixed=DeAdBeEf;other=DEADBEEE;";
+    let single = aobscan::PatternBuilder::from_hex_ascii_ci("DEADBEEF")
+        .unwrap()
+        .with_dual_anchor(false)
+        .build();
+    let dual = aobscan::PatternBuilder::from_hex_ascii_ci("DEADBEEF")
+        .unwrap()
+        .build();
+    assert_eq!(dual.scan_all(data), single.scan_all(data));
+
+    let data = [0xAAu8; 0x40];
+    let single = aobscan::PatternBuilder::from_ida_style("? AA ? AA")
+        .unwrap()
+        .with_overlapping(false)
+        .with_dual_anchor(false)
+        .build();
+    let dual = aobscan::PatternBuilder::from_ida_style("? AA ? AA")
+        .unwrap()
+        .with_overlapping(false)
+        .build();
+    assert_eq!(dual.scan_all(&data), single.scan_all(&data));
+}
@@ -0,0 +1,25 @@
+#[test]
+/// Tests that the estimate is positive and scales with the length of the data.
+fn estimate_duration_scales() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? ? ? ? 05")
+        .unwrap()
+        .build();
+
+    let small = pattern.estimate_duration(0x100000);
+    let large = pattern.estimate_duration(0x1000000);
+
+    assert!(small > std::time::Duration::ZERO);
+    let ratio = large.as_secs_f64() / small.as_secs_f64();
+    assert!((15.0..=17.0).contains(&ratio));
+
+    assert_eq!(pattern.estimate_duration(0), std::time::Duration::ZERO);
+}
+
+#[test]
+/// Tests that more threads reduce the estimate.
+fn estimate_duration_threads() {
+    let single = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], 1);
+    let multi = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], 4);
+
+    assert!(multi.estimate_duration(0x100000) < single.estimate_duration(0x100000));
+}
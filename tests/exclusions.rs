@@ -0,0 +1,30 @@
+#[test]
+/// Tests that matches followed by an excluded byte are rejected.
+fn exclusion_after_match() {
+    // A call (E8) and a jump (E9) after the same two bytes.
+    let data = b"\x90\x48\x8B\xE8\x90\x48\x8B\xE9\x90";
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .with_exclusion(2, 0xE8)
+        .build();
+
+    assert_eq!(pattern.scan_all(data), vec![5]);
+}
+
+#[test]
+/// Tests that exclusions can look behind the match, and that they are not
+/// triggered outside of the data.
+fn exclusion_before_match() {
+    let data = b"\x48\x8B\x90\xCC\x48\x8B\x90\x00\x48\x8B";
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .with_exclusion(-1, 0xCC)
+        .with_exclusion(2, 0xCC)
+        .build();
+
+    // The first match has no byte before it, the second one is preceded by CC,
+    // and the last one has no byte after it.
+    assert_eq!(pattern.scan_all(data), vec![0, 8]);
+}
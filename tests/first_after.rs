@@ -0,0 +1,34 @@
+#[test]
+/// Tests that only the first match at or after the minimum offset is returned.
+fn first_after_offsets() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x400];
+    for offset in [0x10, 0x100, 0x200, 0x300] {
+        data[offset..offset + 4].copy_from_slice(&[0x48, 0x8B, 0x00, 0x05]);
+    }
+
+    assert_eq!(pattern.first_after(&data, 0), Some(0x10));
+    assert_eq!(pattern.first_after(&data, 0x11), Some(0x100));
+    assert_eq!(pattern.first_after(&data, 0x100), Some(0x100));
+    assert_eq!(pattern.first_after(&data, 0x150), Some(0x200));
+    assert_eq!(pattern.first_after(&data, 0x301), None);
+    assert_eq!(pattern.first_after(&data, usize::MAX), None);
+}
+
+#[test]
+/// Tests that the leading wildcards of the pattern are part of the match.
+fn first_after_leading_wildcards() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? ? 8B 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x40];
+    data[0x12..0x14].copy_from_slice(&[0x8B, 0x05]);
+    data[0x32..0x34].copy_from_slice(&[0x8B, 0x05]);
+
+    assert_eq!(pattern.first_after(&data, 0x10), Some(0x10));
+    assert_eq!(pattern.first_after(&data, 0x11), Some(0x30));
+}
@@ -0,0 +1,37 @@
+#[test]
+/// Tests that each block reports its first match, or none.
+fn first_per_block_offsets() {
+    let mut data = vec![0u8; 0x400];
+    // Block 1: a single match.
+    data[0x120..0x122].copy_from_slice(&[0x48, 0x8B]);
+    // Block 2: multiple matches.
+    data[0x210..0x212].copy_from_slice(&[0x48, 0x8B]);
+    data[0x280..0x282].copy_from_slice(&[0x48, 0x8B]);
+    data[0x2F0..0x2F2].copy_from_slice(&[0x48, 0x8B]);
+    // Block 3: a match that crosses the end of the block.
+    data[0x3FF] = 0x48;
+
+    for threads in [1, 2, 3, 8] {
+        let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], threads);
+        assert_eq!(
+            pattern.first_per_block(&data, 0x100),
+            vec![None, Some(0x120), Some(0x210), None],
+        );
+    }
+}
+
+#[test]
+/// Tests that the matches crossing a block boundary belong to the block where they start.
+fn first_per_block_boundary() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x30];
+    data[0x0F..0x12].copy_from_slice(&[0x48, 0x8B, 0x05]);
+
+    // The last block is shorter than the others.
+    assert_eq!(pattern.first_per_block(&data, 0x10), vec![Some(0x0F), None, None]);
+    assert_eq!(pattern.first_per_block(&data[..0x25], 0x10), vec![Some(0x0F), None, None]);
+    assert!(pattern.first_per_block(&[], 0x10).is_empty());
+}
@@ -0,0 +1,36 @@
+#[test]
+/// Tests that the fixed bytes are split at the wildcard gaps.
+fn fixed_fragments_gaps() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? ? 90 90")
+        .unwrap()
+        .build();
+
+    assert_eq!(
+        pattern.fixed_fragments(),
+        vec![(0, vec![0x48, 0x8B]), (4, vec![0x90, 0x90])],
+    );
+}
+
+#[test]
+/// Tests that the offsets include the leading wildcards, and that the partially
+/// fixed and optional bytes split the fragments.
+fn fixed_fragments_offsets() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? ? E8 ? ? ? ? 48 (90) C3 CC ? ?")
+        .unwrap()
+        .build();
+    assert_eq!(
+        pattern.fixed_fragments(),
+        vec![(2, vec![0xE8]), (7, vec![0x48]), (9, vec![0xC3, 0xCC])],
+    );
+
+    let pattern = aobscan::PatternBuilder::from_bitmask(&[0x48, 0x8B, 0x05], "111111111111000011111111")
+        .unwrap()
+        .build();
+    assert_eq!(pattern.fixed_fragments(), vec![(0, vec![0x48]), (2, vec![0x05])]);
+
+    let pattern = aobscan::Pattern::new(vec![0x48, 0x8B, 0x05], vec![true, true, true], 1);
+    assert_eq!(pattern.fixed_fragments(), vec![(0, vec![0x48, 0x8B, 0x05])]);
+
+    let pattern = aobscan::Pattern::new(vec![], vec![], 1);
+    assert!(pattern.fixed_fragments().is_empty());
+}
@@ -0,0 +1,73 @@
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Patterns whose longest run of fixed bytes is not at their start.
+const FRAGMENT_PATTERNS: [&str; 5] = [
+    "? ? ? 90 90 90 90 ? ?",
+    "48 ? ? 8B 05 ? 90 90 E8",
+    "? 00 00 ? 48 8B ? ? ? 48 8B",
+    "E8 ? 48 ? ? ? 8B 00 48 00 ? 5A",
+    "5A ? (48) 8B 00 ? 90 90 90 90",
+];
+
+/// Builds the pattern with the given anchoring mode.
+fn build(pattern: &str, dual_anchor: bool) -> aobscan::Pattern {
+    aobscan::PatternBuilder::from_ida_style(pattern)
+        .unwrap()
+        .with_dual_anchor(dual_anchor)
+        .build()
+}
+
+#[test]
+/// Tests that the longest fragment reports the same matches as the first byte anchor,
+/// and as a comparison at every offset, on random data.
+fn fragment_anchor_same_matches() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x995);
+    let mut data = vec![0u8; 0x10000];
+    rng.fill_bytes(&mut data);
+    // Skew the data towards a few values, so that the fragments match more often.
+    for byte in data.iter_mut() {
+        if rng.gen_bool(0.75) {
+            *byte = [0x00, 0x05, 0x48, 0x5A, 0x8B, 0x90, 0xE8][rng.gen_range(0..7)];
+        }
+    }
+
+    for (index, pattern) in FRAGMENT_PATTERNS.iter().enumerate() {
+        // Plant a few matches, keeping the random bytes in place of the wildcards.
+        let mut data = data.clone();
+        for offset in (0x100 * index..data.len() - 0x10).step_by(0x1234) {
+            for (position, token) in pattern.split(' ').enumerate() {
+                if let Ok(byte) = u8::from_str_radix(token.trim_matches(['(', ')']), 16) {
+                    data[offset + position] = byte;
+                }
+            }
+        }
+
+        let single = build(pattern, false);
+        let expected = single.scan_all(&data);
+        assert!(!expected.is_empty(), "{}", pattern);
+        assert_eq!(build(pattern, true).scan_all(&data), expected, "{}", pattern);
+
+        let naive = (0..data.len()).filter(|&offset| single.matches_at(&data, offset)).collect::<Vec<_>>();
+        assert_eq!(expected, naive, "{}", pattern);
+    }
+}
+
+#[test]
+/// Tests that the matches whose fragment ends with the data, and the non-overlapping
+/// matches, are reported as with the first byte anchor.
+fn fragment_anchor_edges() {
+    let data = [0x90u8; 0x20];
+    for overlapping in [true, false] {
+        let build = |dual_anchor| {
+            aobscan::PatternBuilder::from_ida_style("? ? ? 90 90 90 90")
+                .unwrap()
+                .with_overlapping(overlapping)
+                .with_dual_anchor(dual_anchor)
+                .build()
+        };
+
+        let expected = build(false).scan_all(&data);
+        assert_eq!(expected.last(), Some(&if overlapping { 0x19 } else { 0x15 }));
+        assert_eq!(build(true).scan_all(&data), expected);
+    }
+}
@@ -0,0 +1,46 @@
+#[test]
+/// Tests that the hint points after the `ret` that precedes the match.
+fn instruction_hint_after_ret() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0x90u8; 0x100];
+    // The end of the previous function, followed by the prologue and the match.
+    data[0x45] = 0xC3;
+    data[0x46..0x4A].copy_from_slice(&[0x55, 0x48, 0x89, 0xE5]);
+    data[0x4A..0x4D].copy_from_slice(&[0x48, 0x8B, 0x05]);
+
+    let mut hints = vec![];
+    pattern.scan_with_instruction_hint(&data, |offset, hint| {
+        hints.push((offset, hint));
+        true
+    });
+
+    assert_eq!(hints, vec![(0x4A, 0x46)]);
+}
+
+#[test]
+/// Tests that the hint falls back to the aligned boundary when there is no
+/// `ret` or `int3` near the match.
+fn instruction_hint_aligned() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0x90u8; 0x100];
+    data[0x4A..0x4D].copy_from_slice(&[0x48, 0x8B, 0x05]);
+    // Too far from the match to be considered.
+    data[0x10] = 0xCC;
+    // Right before the match.
+    data[0xC1] = 0xCC;
+    data[0xC2..0xC5].copy_from_slice(&[0x48, 0x8B, 0x05]);
+
+    let mut hints = vec![];
+    pattern.scan_with_instruction_hint(&data, |offset, hint| {
+        hints.push((offset, hint));
+        true
+    });
+
+    assert_eq!(hints, vec![(0x4A, 0x40), (0xC2, 0xC2)]);
+}
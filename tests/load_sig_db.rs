@@ -0,0 +1,49 @@
+#[test]
+/// Tests that the named signatures of a database are parsed in order.
+fn load_sig_db_valid() {
+    let db = "GetPlayer = 48 8B 05 ? ? ? ?\n\
+              \n\
+              EntityList=48 8D 0D ? ? ? ? E8\n";
+
+    let patterns = aobscan::load_sig_db(db).unwrap();
+    assert_eq!(patterns.len(), 2);
+    assert_eq!(patterns[0].0, "GetPlayer");
+    assert_eq!(patterns[0].1, aobscan::PatternBuilder::from_ida_style("48 8B 05 ? ? ? ?").unwrap().build());
+    assert_eq!(patterns[1].0, "EntityList");
+    assert_eq!(patterns[1].1.to_ida_style(), "48 8D 0D ? ? ? ? E8");
+}
+
+#[test]
+/// Tests that the comment lines are ignored.
+fn load_sig_db_comments() {
+    let db = "# Signatures for version 1.2\n\
+              \x20 # Indented comment = 90 90\n\
+              Nop = 90\n";
+
+    let patterns = aobscan::load_sig_db(db).unwrap();
+    assert_eq!(patterns.len(), 1);
+    assert_eq!(patterns[0].0, "Nop");
+    assert!(aobscan::load_sig_db("# Empty database\n").unwrap().is_empty());
+}
+
+#[test]
+/// Tests that the malformed lines are reported with their number.
+fn load_sig_db_malformed() {
+    let db = "# Header\nGetPlayer = 48 8B 05\nEntityList = 48 ZZ\n";
+    match aobscan::load_sig_db(db) {
+        Err(aobscan::BuilderError::AtLine { line, error }) => {
+            assert_eq!(line, 3);
+            assert!(matches!(*error, aobscan::BuilderError::ParseAt { .. }));
+        }
+        result => panic!("unexpected result: {:?}", result),
+    }
+
+    for db in ["Nop 90", "= 90"] {
+        match aobscan::load_sig_db(db) {
+            Err(aobscan::BuilderError::AtLine { line: 1, error }) => {
+                assert!(matches!(*error, aobscan::BuilderError::InvalidSignature(_)));
+            }
+            result => panic!("unexpected result: {:?}", result),
+        }
+    }
+}
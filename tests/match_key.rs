@@ -0,0 +1,32 @@
+use std::collections::HashSet;
+
+#[test]
+/// Tests that patterns differing only in their thread count share the same match key.
+fn match_key_ignores_threads() {
+    let single = aobscan::Pattern::new(vec![0x48, 0x8B, 0x00], vec![true, true, false], 1);
+    let multi = aobscan::Pattern::new(vec![0x48, 0x8B, 0x00], vec![true, true, false], 4);
+
+    assert_ne!(single, multi);
+    assert_eq!(single.match_key(), multi.match_key());
+
+    let keys = [single.match_key(), multi.match_key()].into_iter().collect::<HashSet<_>>();
+    assert_eq!(keys.len(), 1);
+}
+
+#[test]
+/// Tests that patterns matching different bytes have different match keys.
+fn match_key_differs() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 90")
+        .unwrap()
+        .build();
+    let other_bytes = aobscan::PatternBuilder::from_ida_style("48 8B ? 91")
+        .unwrap()
+        .build();
+    let other_options = aobscan::PatternBuilder::from_ida_style("48 8B ? 90")
+        .unwrap()
+        .with_overlapping(false)
+        .build();
+
+    assert_ne!(pattern.match_key(), other_bytes.match_key());
+    assert_ne!(pattern.match_key(), other_options.match_key());
+}
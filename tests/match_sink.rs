@@ -0,0 +1,67 @@
+use aobscan::{CountSink, FirstSink, MatchSink, VecSink};
+
+/// Builds a buffer with matches of `48 8B 05` at 0x10, 0x20 and 0x30.
+fn planted_data() -> Vec<u8> {
+    let mut data = vec![0u8; 0x40];
+    for offset in [0x10, 0x20, 0x30] {
+        data[offset..offset + 3].copy_from_slice(&[0x48, 0x8B, 0x05]);
+    }
+    data
+}
+
+/// Builds the pattern used by the tests.
+fn pattern() -> aobscan::Pattern {
+    aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build()
+}
+
+#[test]
+/// Tests that the vector sink collects all the matches.
+fn vec_sink() {
+    let mut sink = VecSink::default();
+    assert!(pattern().scan_sink(&planted_data(), &mut sink));
+    assert_eq!(sink.offsets, vec![0x10, 0x20, 0x30]);
+}
+
+#[test]
+/// Tests that the count sink counts all the matches.
+fn count_sink() {
+    let mut sink = CountSink::default();
+    assert!(pattern().scan_sink(&planted_data(), &mut sink));
+    assert_eq!(sink.count, 3);
+
+    let mut sink = CountSink::default();
+    assert!(!pattern().scan_sink(&[0u8; 0x40], &mut sink));
+    assert_eq!(sink.count, 0);
+}
+
+#[test]
+/// Tests that the first sink stops the scan at the first match.
+fn first_sink() {
+    let mut sink = FirstSink::default();
+    assert!(pattern().scan_sink(&planted_data(), &mut sink));
+    assert_eq!(sink.offset, Some(0x10));
+
+    let mut sink = FirstSink::default();
+    assert!(!pattern().scan_sink(&[0u8; 0x40], &mut sink));
+    assert_eq!(sink.offset, None);
+}
+
+#[test]
+/// Tests that custom sinks can stop the scan.
+fn custom_sink() {
+    /// Collects the matches until the limit is reached.
+    struct LimitSink(Vec<usize>, usize);
+
+    impl MatchSink for LimitSink {
+        fn accept(&mut self, offset: usize) -> bool {
+            self.0.push(offset);
+            self.0.len() < self.1
+        }
+    }
+
+    let mut sink = LimitSink(vec![], 2);
+    pattern().scan_sink(&planted_data(), &mut sink);
+    assert_eq!(sink.0, vec![0x10, 0x20]);
+}
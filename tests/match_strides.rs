@@ -0,0 +1,28 @@
+#[test]
+/// Tests that evenly-spaced matches produce a constant stride.
+fn match_strides_constant() {
+    let mut data = vec![0u8; 0x1000];
+    for offset in (0x100..0x900).step_by(0x80) {
+        data[offset..offset + 3].copy_from_slice(&[0x48, 0x8B, 0x05]);
+    }
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    assert_eq!(pattern.match_strides(&data), vec![0x80; 15]);
+}
+
+#[test]
+/// Tests that less than two matches produce no strides.
+fn match_strides_empty() {
+    let mut data = vec![0u8; 0x100];
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    assert!(pattern.match_strides(&data).is_empty());
+
+    data[0x10..0x13].copy_from_slice(&[0x48, 0x8B, 0x05]);
+    assert!(pattern.match_strides(&data).is_empty());
+}
@@ -0,0 +1,31 @@
+#[test]
+/// Tests that only the start of the data is checked for a prefix match.
+fn matches_prefix_header() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("7F 45 4C 46 ? 01")
+        .unwrap()
+        .build();
+
+    assert!(pattern.matches_prefix(b"\x7FELF\x02\x01\x01\x00"));
+    // The header must be at offset 0.
+    assert!(!pattern.matches_prefix(b"\x00\x7FELF\x02\x01\x01"));
+    // The header must fit in the data.
+    assert!(!pattern.matches_prefix(b"\x7FELF\x02"));
+    assert!(!pattern.matches_prefix(&[]));
+}
+
+#[test]
+/// Tests that only the candidates at which the pattern matches are returned.
+fn matches_at_any_candidates() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x100];
+    data[0x10..0x14].copy_from_slice(&[0x48, 0x8B, 0x00, 0x05]);
+    data[0x80..0x84].copy_from_slice(&[0x48, 0x8B, 0x01, 0x05]);
+    data[0xFE..0x100].copy_from_slice(&[0x48, 0x8B]);
+
+    let candidates = [0x80, 0x11, 0x10, 0xFE, 0x40, usize::MAX];
+    assert_eq!(pattern.matches_at_any(&data, &candidates), vec![0x80, 0x10]);
+    assert!(pattern.matches_at_any(&data, &[]).is_empty());
+}
@@ -0,0 +1,37 @@
+use aobscan::IdaScriptAction;
+
+#[test]
+/// Tests that the script contains a line with the address of each match.
+fn matches_to_ida_script_actions() {
+    let matches = [0x140001000, 0x140002F30];
+
+    assert_eq!(
+        aobscan::matches_to_ida_script(&matches, "CreateFileW wrapper", IdaScriptAction::Comment),
+        "import idc\n\
+         idc.set_cmt(0x140001000, \"CreateFileW wrapper\", 0)\n\
+         idc.set_cmt(0x140002f30, \"CreateFileW wrapper\", 0)\n"
+    );
+
+    assert_eq!(
+        aobscan::matches_to_ida_script(&matches, "hit", IdaScriptAction::Bookmark),
+        "import idc\n\
+         idc.put_bookmark(0x140001000, 0, 0, 0, 1, \"hit\")\n\
+         idc.put_bookmark(0x140002f30, 0, 0, 0, 2, \"hit\")\n"
+    );
+
+    assert_eq!(
+        aobscan::matches_to_ida_script(&matches, "ignored", IdaScriptAction::Breakpoint),
+        "import idc\nidc.add_bpt(0x140001000)\nidc.add_bpt(0x140002f30)\n"
+    );
+
+    assert_eq!(aobscan::matches_to_ida_script(&[], "none", IdaScriptAction::Comment), "import idc\n");
+}
+
+#[test]
+/// Tests that the comments are escaped in the Python string literals.
+fn matches_to_ida_script_escaping() {
+    assert_eq!(
+        aobscan::matches_to_ida_script(&[0x10], "say \"hi\"\\\n\x07", IdaScriptAction::Comment),
+        "import idc\nidc.set_cmt(0x10, \"say \\\"hi\\\"\\\\\\n\\x07\", 0)\n"
+    );
+}
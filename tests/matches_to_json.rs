@@ -0,0 +1,18 @@
+#![cfg(feature = "serde")]
+
+#[test]
+/// Tests that the exported JSON contains the pattern and the offsets of the matches.
+fn matches_to_json() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 90")
+        .unwrap()
+        .build();
+
+    let json = aobscan::matches_to_json(&pattern, &[0x10, 0x1000]);
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+    assert_eq!(value["pattern"], "48 8B ? 90");
+    assert_eq!(
+        serde_json::from_value::<Vec<usize>>(value["offsets"].clone()).unwrap(),
+        vec![0x10, 0x1000]
+    );
+}
@@ -0,0 +1,32 @@
+#[test]
+/// Tests that the matches closer than the minimum distance to the last
+/// reported match are suppressed.
+fn min_distance_suppression() {
+    let mut data = vec![0u8; 0x200];
+    for offset in [0x10, 0x14, 0x1C, 0x20, 0x40, 0x44, 0x100] {
+        data[offset..offset + 2].copy_from_slice(&[0x48, 0x8B]);
+    }
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .with_min_distance(0x10)
+        .build();
+
+    // 0x14 and 0x1C are within 0x10 bytes of 0x10, while 0x20 is not.
+    assert_eq!(pattern.scan_all(&data), vec![0x10, 0x20, 0x40, 0x100]);
+}
+
+#[test]
+/// Tests that all the matches are reported without a minimum distance.
+fn min_distance_disabled() {
+    let mut data = vec![0u8; 0x100];
+    for offset in [0x10, 0x12, 0x14] {
+        data[offset..offset + 2].copy_from_slice(&[0x48, 0x8B]);
+    }
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build();
+
+    assert_eq!(pattern.scan_all(&data), vec![0x10, 0x12, 0x14]);
+}
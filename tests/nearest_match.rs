@@ -0,0 +1,36 @@
+/// Builds a buffer with matches of `48 8B` at the given offsets.
+fn planted_data(offsets: &[usize]) -> Vec<u8> {
+    let mut data = vec![0u8; 0x200];
+    for &offset in offsets {
+        data[offset..offset + 2].copy_from_slice(&[0x48, 0x8B]);
+    }
+    data
+}
+
+#[test]
+/// Tests that the nearest match is returned, whichever side of the target it is on.
+fn nearest_match_both_sides() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build();
+    let data = planted_data(&[0x20, 0x80, 0x110, 0x1F0]);
+
+    assert_eq!(pattern.nearest_match(&data, 0x100), Some(0x110));
+    assert_eq!(pattern.nearest_match(&data, 0x90), Some(0x80));
+    assert_eq!(pattern.nearest_match(&data, 0x80), Some(0x80));
+    assert_eq!(pattern.nearest_match(&data, 0x00), Some(0x20));
+    assert_eq!(pattern.nearest_match(&data, usize::MAX), Some(0x1F0));
+
+    // Equally distant matches prefer the one before the target.
+    assert_eq!(pattern.nearest_match(&data, 0x50), Some(0x20));
+}
+
+#[test]
+/// Tests that there is no nearest match in data without matches.
+fn nearest_match_none() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build();
+
+    assert_eq!(pattern.nearest_match(&planted_data(&[]), 0x100), None);
+}
@@ -0,0 +1,66 @@
+#![cfg(feature = "object-scan")]
+
+use object::{BinaryFormat, SectionKind};
+use object::macho::CPU_TYPE_X86_64;
+
+use aobscan::ObjectKind;
+use common::*;
+
+mod common;
+
+static WASM_MODULE: &[u8] = include_bytes!("fixtures/planted.wasm");
+
+/// Builds the headers of a 64-bit PE file, which are enough for its format to be detected.
+fn pe_headers() -> Vec<u8> {
+    let mut pe = vec![0u8; 0x200];
+    pe[0..2].copy_from_slice(b"MZ");
+    // Offset of the NT headers.
+    pe[0x3C..0x40].copy_from_slice(&0x40u32.to_le_bytes());
+    pe[0x40..0x44].copy_from_slice(b"PE\0\0");
+    // Magic of the optional header, after the file header.
+    pe[0x58..0x5A].copy_from_slice(&0x20Bu16.to_le_bytes());
+    pe
+}
+
+/// Builds a Unix archive with a single member.
+fn archive() -> Vec<u8> {
+    let mut archive = b"!<arch>\n".to_vec();
+    archive.extend(format!("{:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n", "a.o/", 0, 0, 0, 644, 4).as_bytes());
+    archive.extend(b"\x90\x90\x90\x90");
+    archive
+}
+
+#[test]
+/// Tests that the format of the object files is detected.
+fn object_kind_formats() {
+    let text: &[(&str, SectionKind, &[u8])] = &[("__text", SectionKind::Text, &[0x90; 0x10])];
+    let macho = build_object(BinaryFormat::MachO, text);
+
+    assert_eq!(aobscan::object_kind(&build_object(BinaryFormat::Elf, text)), Some(ObjectKind::Elf));
+    assert_eq!(aobscan::object_kind(&macho), Some(ObjectKind::MachO));
+    assert_eq!(aobscan::object_kind(&build_fat(&[(CPU_TYPE_X86_64, &macho)])), Some(ObjectKind::MachOFat));
+    assert_eq!(aobscan::object_kind(&build_object(BinaryFormat::Coff, text)), Some(ObjectKind::Coff));
+    assert_eq!(aobscan::object_kind(&pe_headers()), Some(ObjectKind::Pe));
+    assert_eq!(aobscan::object_kind(&archive()), Some(ObjectKind::Archive));
+    assert_eq!(aobscan::object_kind(WASM_MODULE), Some(ObjectKind::Wasm));
+
+    assert_eq!(aobscan::object_kind(&[0x90; 0x100]), None);
+    assert_eq!(aobscan::object_kind(&[]), None);
+}
+
+#[test]
+/// Tests that only the object files that can be scanned are reported as such.
+fn is_object_file() {
+    let text: &[(&str, SectionKind, &[u8])] = &[("__text", SectionKind::Text, &[0x90; 0x10])];
+    let macho = build_object(BinaryFormat::MachO, text);
+
+    assert!(aobscan::is_object_file(&build_object(BinaryFormat::Elf, text)));
+    assert!(aobscan::is_object_file(&macho));
+    assert!(aobscan::is_object_file(&build_fat(&[(CPU_TYPE_X86_64, &macho)])));
+    assert!(aobscan::is_object_file(&build_object(BinaryFormat::Coff, text)));
+    assert!(aobscan::is_object_file(WASM_MODULE));
+
+    assert!(!aobscan::is_object_file(&archive()));
+    assert!(!aobscan::is_object_file(&[0x90; 0x100]));
+    assert!(!aobscan::is_object_file(&[]));
+}
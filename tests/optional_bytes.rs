@@ -0,0 +1,81 @@
+#[test]
+/// Tests that the matches are reported both with and without the optional byte.
+fn optional_byte_present_and_absent() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("(48) 8B 05 ? ? ? ? C3")
+        .unwrap()
+        .build();
+    assert_eq!(pattern.len(), 8);
+    assert_eq!(pattern.min_len(), 7);
+
+    let mut data = vec![0u8; 0x100];
+    // Present.
+    data[0x10..0x18].copy_from_slice(&[0x48, 0x8B, 0x05, 0x11, 0x22, 0x33, 0x44, 0xC3]);
+    // Absent.
+    data[0x80..0x87].copy_from_slice(&[0x8B, 0x05, 0x11, 0x22, 0x33, 0x44, 0xC3]);
+
+    // The match with the optional byte also contains the match without it.
+    assert_eq!(pattern.scan_all(&data), vec![0x10, 0x11, 0x80]);
+}
+
+#[test]
+/// Tests that the optional bytes in the middle of the pattern are matched in both
+/// cases, also at the end of the data.
+fn optional_byte_middle() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("55 (48) 89 E5")
+        .unwrap()
+        .build();
+
+    assert!(pattern.matches_at(&[0x55, 0x48, 0x89, 0xE5], 0));
+    assert!(pattern.matches_at(&[0x55, 0x89, 0xE5], 0));
+    assert!(!pattern.matches_at(&[0x55, 0x90, 0x89, 0xE5], 0));
+
+    // The shortest match ends with the data.
+    let data = [0x90, 0x90, 0x55, 0x89, 0xE5];
+    assert_eq!(pattern.scan_all(&data), vec![2]);
+    assert_eq!(pattern.to_ida_style(), "55 (48) 89 E5");
+}
+
+#[test]
+/// Tests that the patterns made only of optional bytes are rejected, and that
+/// the invalid optional bytes are reported.
+fn optional_byte_invalid() {
+    assert!(matches!(
+        aobscan::PatternBuilder::from_ida_style("(48) ?"),
+        Err(aobscan::BuilderError::InvalidSignature(_))
+    ));
+    assert_eq!(
+        aobscan::PatternBuilder::from_ida_style("8B (4Z)").err(),
+        Some(aobscan::BuilderError::ParseAt { index: 3, token: "(4Z)".to_string() })
+    );
+}
+
+
+#[test]
+/// Tests that the span of the matches that omit an optional byte is their actual length,
+/// including a match that ends with the data.
+fn optional_byte_match_span() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("90 (48) 8B")
+        .unwrap()
+        .build();
+
+    let matches = pattern.scan_matches(&[0x00, 0x90, 0x8B]);
+    assert_eq!(matches.len(), 1);
+    assert_eq!((matches[0].offset, matches[0].end), (1, 3));
+    assert_eq!(matches[0].bytes, vec![0x90, 0x8B]);
+
+    let data = [0x90, 0x8B, 0x00, 0x00, 0x00];
+    let matches = pattern.scan_matches(&data);
+    assert_eq!((matches[0].offset, matches[0].end), (0, 2));
+    assert_eq!(matches[0].bytes, vec![0x90, 0x8B]);
+
+    assert!(pattern.covers(&data, 1));
+    assert!(!pattern.covers(&data, 2));
+    assert_eq!(pattern.matches_covering(&data, 2), Vec::<usize>::new());
+
+    let mut distances = vec![];
+    pattern.scan_from_end(&data, |distance| {
+        distances.push(distance);
+        true
+    });
+    assert_eq!(distances, vec![3]);
+}
@@ -51,6 +51,80 @@ fn code_pattern() {
     );
 }
 
+#[test]
+fn cheat_engine_pattern() {
+    assert!(
+        // Valid Cheat Engine pattern (mix of * and ** is allowed)
+        aobscan::PatternBuilder::from_cheat_engine("48 8B 05 * * ** *").is_ok()
+    );
+
+    assert!(
+        // Invalid Cheat Engine pattern (empty pattern)
+        aobscan::PatternBuilder::from_cheat_engine("").is_err()
+    );
+
+    assert!(
+        // Invalid Cheat Engine pattern (IDA-style wildcard)
+        aobscan::PatternBuilder::from_cheat_engine("48 8B 05 ? ? ? ?").is_err()
+    );
+
+    assert!(
+        // Invalid Cheat Engine pattern (one-char byte)
+        aobscan::PatternBuilder::from_cheat_engine("48 8B 5").is_err()
+    );
+
+    // Cheat Engine and IDA patterns with the same bytes are equivalent.
+    let mut data = vec![0u8; 0x100];
+    data[0x40..0x47].copy_from_slice(&[0x48, 0x8B, 0x05, 0x11, 0x22, 0x33, 0x44]);
+    data[0x80..0x87].copy_from_slice(&[0x48, 0x8B, 0x05, 0x55, 0x66, 0x77, 0x88]);
+
+    let cheat_engine = aobscan::PatternBuilder::from_cheat_engine("48 8B 05 * * * *")
+        .unwrap()
+        .build();
+    let ida = aobscan::PatternBuilder::from_ida_style("48 8B 05 ? ? ? ?")
+        .unwrap()
+        .build();
+
+    assert_eq!(cheat_engine, ida);
+    assert_eq!(cheat_engine.scan_all(&data), vec![0x40, 0x80]);
+}
+
+#[test]
+fn typed_pattern() {
+    use aobscan::{Mask, Signature};
+
+    assert_eq!(
+        // Valid typed pattern
+        aobscan::PatternBuilder::from_signature_mask(
+            Signature::from(&b"\x48\x8B\x00\x90"[..]),
+            Mask(vec![true, true, false, true]),
+        ).unwrap().build(),
+        aobscan::PatternBuilder::from_ida_style("48 8B ? 90").unwrap().build()
+    );
+
+    assert!(
+        // Invalid typed pattern (length of signature and mask don't match)
+        aobscan::PatternBuilder::from_signature_mask(
+            Signature(vec![0x48, 0x8B]),
+            Mask::from(&[true][..]),
+        ).is_err()
+    );
+}
+
+#[test]
+fn optional_bytes_pattern() {
+    assert_eq!(
+        // Wildcards are represented by `None`
+        aobscan::PatternBuilder::from_iter_opt([Some(0x48), None, Some(0x90)]).unwrap().build(),
+        aobscan::PatternBuilder::from_ida_style("48 ? 90").unwrap().build()
+    );
+
+    assert!(
+        // Invalid sequence (empty sequence)
+        aobscan::PatternBuilder::from_iter_opt(Vec::<Option<u8>>::new()).is_err()
+    );
+}
+
 #[test]
 fn hex_pattern() {
     assert!(
@@ -93,3 +167,121 @@ fn hex_pattern() {
         aobscan::PatternBuilder::from_hex_string("?").is_err()
     );
 }
+
+#[test]
+fn detect_pattern_style() {
+    use aobscan::PatternStyle;
+
+    // Spaced patterns are detected as IDA-style.
+    let (pattern, style) = aobscan::detect_and_parse("48 8B ? ? 90").unwrap();
+    assert_eq!(style, PatternStyle::Ida);
+    assert_eq!(pattern, aobscan::PatternBuilder::from_ida_style("48 8B ? ? 90").unwrap().build());
+
+    // Packed patterns are detected as hexadecimal strings.
+    let (pattern, style) = aobscan::detect_and_parse("488b????90").unwrap();
+    assert_eq!(style, PatternStyle::Hex);
+    assert_eq!(pattern, aobscan::PatternBuilder::from_hex_string("488b????90").unwrap().build());
+
+    // Invalid patterns fail in every style.
+    assert!(aobscan::detect_and_parse("48 8B ZZ").is_err());
+    assert!(aobscan::detect_and_parse("488bZZ").is_err());
+    assert!(aobscan::detect_and_parse("").is_err());
+}
+
+#[test]
+fn ida_style_formatting() {
+    assert_eq!(
+        aobscan::PatternBuilder::from_ida_style("? 48 8b ?? 90 ? ?").unwrap().build().to_ida_style(),
+        // Trailing wildcards are removed when the pattern is created.
+        "? 48 8B ? 90"
+    );
+
+    assert_eq!(
+        aobscan::PatternBuilder::from_hex_string("488b????").unwrap().build().to_ida_style(),
+        "48 8B"
+    );
+}
+
+#[test]
+/// Tests that the parse errors report the index and the content of the invalid token.
+fn parse_error_position() {
+    use aobscan::BuilderError;
+
+    assert_eq!(
+        aobscan::PatternBuilder::from_ida_style("48 8B ? ZA 90").unwrap_err(),
+        BuilderError::ParseAt { index: 8, token: "ZA".to_string() }
+    );
+
+    assert_eq!(
+        // The index is in bytes, and accounts for repeated whitespaces.
+        aobscan::PatternBuilder::from_ida_style("48   8B\t+1").unwrap_err(),
+        BuilderError::ParseAt { index: 8, token: "+1".to_string() }
+    );
+
+    assert_eq!(
+        aobscan::PatternBuilder::from_hex_string("488b??0g90").unwrap_err(),
+        BuilderError::ParseAt { index: 6, token: "0g".to_string() }
+    );
+
+    assert_eq!(
+        aobscan::PatternBuilder::from_cheat_engine("48 8B 5 *").unwrap_err(),
+        BuilderError::ParseAt { index: 6, token: "5".to_string() }
+    );
+}
+
+#[test]
+fn hex_ascii_case_insensitive_pattern() {
+    let pattern = aobscan::PatternBuilder::from_hex_ascii_ci("DEADBEEF")
+        .unwrap()
+        .build();
+
+    let data = b"key=deadbeef;KEY=DEADBEEF;mixed=DeAdBeEf;other=DEADBEEE;";
+    assert_eq!(pattern.scan_all(data), vec![4, 17, 32]);
+
+    // Digits are matched exactly. ('0' and 'P' only differ by the case bit)
+    let pattern = aobscan::PatternBuilder::from_hex_ascii_ci("c0?e")
+        .unwrap()
+        .build();
+    assert_eq!(pattern.scan_all(b"C0FE cPfe C0fE"), vec![0, 10]);
+
+    assert!(
+        // Invalid pattern (empty pattern)
+        aobscan::PatternBuilder::from_hex_ascii_ci("").is_err()
+    );
+
+    assert_eq!(
+        // Invalid pattern (non-hexadecimal character)
+        aobscan::PatternBuilder::from_hex_ascii_ci("DEADBEEG").unwrap_err(),
+        aobscan::BuilderError::ParseAt { index: 7, token: "G".to_string() }
+    );
+}
+
+#[test]
+fn bitmask_pattern() {
+    // Only the high nibble of the second byte is significant.
+    let pattern = aobscan::PatternBuilder::from_bitmask(b"\x48\x50", "1111111111110000")
+        .unwrap()
+        .build();
+    assert_eq!(
+        pattern.scan_all(&[0x48, 0x50, 0x48, 0x5F, 0x48, 0x60, 0x49, 0x50]),
+        vec![0, 2]
+    );
+
+    // The bits of the signature that are not significant are ignored.
+    let pattern = aobscan::PatternBuilder::from_bitmask(b"\x48\xFF", "1111111111110000")
+        .unwrap()
+        .build();
+    assert_eq!(pattern.scan_all(&[0x48, 0xF3]), vec![0]);
+
+    assert_eq!(
+        // Invalid bitmask (length is not 8 times the length of the signature)
+        aobscan::PatternBuilder::from_bitmask(b"\x48\x50", "11111111").unwrap_err(),
+        aobscan::BuilderError::SizeMismatch
+    );
+
+    assert_eq!(
+        // Invalid bitmask (invalid character)
+        aobscan::PatternBuilder::from_bitmask(b"\x48", "1111x111").unwrap_err(),
+        aobscan::BuilderError::ParseAt { index: 4, token: "x".to_string() }
+    );
+}
@@ -0,0 +1,40 @@
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that the pattern generated from two versions matches both of them.
+fn pattern_from_diff_matches_both() {
+    let a = random_bytes_seeded(0x1000, 0x0B5CA4);
+    let mut b = a.clone();
+    b[0x800] ^= 0xFF;
+
+    let pattern = aobscan::pattern_from_diff(&a, &b, 8).unwrap();
+    assert_eq!(pattern.len(), 17);
+    assert_eq!(pattern.to_ida_style().split(' ').nth(8), Some("?"));
+
+    assert_eq!(pattern.scan_all(&a), vec![0x7F8]);
+    assert_eq!(pattern.scan_all(&b), vec![0x7F8]);
+}
+
+#[test]
+/// Tests that the other differences in the window are wildcards, and that the
+/// window is shortened at the edges of the buffers.
+fn pattern_from_diff_edges() {
+    let a = [0x10, 0x20, 0x30, 0x40, 0x50];
+    let b = [0x11, 0x20, 0x31, 0x40];
+
+    let pattern = aobscan::pattern_from_diff(&a, &b, 3).unwrap();
+    assert_eq!(pattern.to_ida_style(), "? 20 ? 40");
+    assert!(pattern.matches_at(&a, 0));
+    assert!(pattern.matches_at(&b, 0));
+}
+
+#[test]
+/// Tests that no pattern is generated from identical buffers.
+fn pattern_from_diff_identical() {
+    let a = [0x48, 0x8B, 0x05];
+    assert!(aobscan::pattern_from_diff(&a, &a, 4).is_none());
+    assert!(aobscan::pattern_from_diff(&a, &a[..2], 4).is_none());
+    assert!(aobscan::pattern_from_diff(&[], &[], 4).is_none());
+}
@@ -0,0 +1,33 @@
+#![cfg(feature = "object-scan")]
+
+use object::{BinaryFormat, SectionKind};
+
+use aobscan::prelude::*;
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that the prelude brings the object scanning methods into scope.
+fn prelude_scan_object() {
+    let mut text = vec![0u8; 0x40];
+    text[0x10..0x13].copy_from_slice(&[0x48, 0x8B, 0x05]);
+    let elf = build_object(BinaryFormat::Elf, &[(".text", SectionKind::Text, &text)]);
+
+    let pattern: Pattern = PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    let mut offsets = vec![];
+    let result: Result<bool, ObjectError> = pattern.scan_object(&elf, ".text", |result: SectionResult| {
+        offsets.push(result.section_offset);
+        true
+    });
+
+    assert_eq!(result, Ok(true));
+    assert_eq!(offsets, vec![0x10]);
+
+    let error: BuilderError = PatternBuilder::from_ida_style("ZZ").unwrap_err();
+    assert!(matches!(error, BuilderError::ParseAt { .. }));
+    assert_eq!(pattern.try_scan(&[], |_| true), Err(ScanError::DataTooSmall));
+}
@@ -0,0 +1,14 @@
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that the same seed always generates the same bytes.
+fn random_bytes_seeded_reproducible() {
+    let first = random_bytes_seeded(0x1000, 0x0B5CA4);
+    assert_eq!(first, random_bytes_seeded(0x1000, 0x0B5CA4));
+    assert_ne!(first, random_bytes_seeded(0x1000, 0x0B5CA5));
+
+    // Shorter buffers are a prefix of the longer ones with the same seed.
+    assert_eq!(random_bytes_seeded(0x100, 0x0B5CA4), first[..0x100]);
+}
@@ -0,0 +1,53 @@
+use rand::{RngCore, SeedableRng};
+
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that long signatures without wildcards find the same matches as the
+/// brute-force scan, including the near misses and the matches across chunks.
+fn rolling_hash_long_needle() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x955);
+    let mut needle = vec![0u8; 256];
+    rng.fill_bytes(&mut needle);
+
+    let mut data = vec![0u8; 0x10000];
+    rng.fill_bytes(&mut data);
+    for offset in [0, 0x1000, 0x1100, 0x7F80, 0x9FF0, 0x10000 - 256] {
+        data[offset..offset + 256].copy_from_slice(&needle);
+    }
+    // Near misses, differing in the first or the last byte.
+    for (offset, index) in [(0x3000, 0), (0x4000, 255), (0x5000, 128)] {
+        data[offset..offset + 256].copy_from_slice(&needle);
+        data[offset + index] ^= 0x01;
+    }
+
+    let mask = vec![true; needle.len()];
+    let expected = naive_scan(&data, &needle, &mask);
+    assert_eq!(expected.len(), 6);
+
+    for threads in 1..=4 {
+        let pattern = aobscan::Pattern::new(needle.clone(), mask.clone(), threads);
+        assert_eq!(pattern.scan_all(&data), expected);
+    }
+}
+
+#[test]
+/// Tests that the rolling hash is resumed correctly after the scan skips positions.
+fn rolling_hash_periodic_needle() {
+    // The needle is periodic, so it matches at every position of a long run.
+    let needle = [0xAB, 0xCD].repeat(40);
+    let mut data = vec![0u8; 0x400];
+    data[0x20..0x220].copy_from_slice(&[0xAB, 0xCD].repeat(0x100));
+
+    let mask = vec![true; needle.len()];
+    let overlapping = aobscan::Pattern::new(needle.clone(), mask.clone(), 1);
+    assert_eq!(overlapping.scan_all(&data), naive_scan(&data, &needle, &mask));
+
+    let non_overlapping = aobscan::PatternBuilder::from_signature_mask(
+        needle.clone().into(),
+        mask.into(),
+    ).unwrap().with_overlapping(false).build();
+    assert_eq!(non_overlapping.scan_all(&data), vec![0x20, 0x70, 0xC0, 0x110, 0x160, 0x1B0]);
+}
@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+#[test]
+/// Tests that the shared matches are sorted, and can be handed off to other threads.
+fn scan_all_shared_sorted() {
+    let mut data = vec![0u8; 0x1000];
+    for offset in [0xF00, 0x10, 0x800, 0x7FF] {
+        data[offset] = 0xCC;
+    }
+
+    for threads in [1, 2, 3, 8] {
+        let pattern = aobscan::Pattern::new(vec![0xCC], vec![true], threads);
+        let matches: Arc<[usize]> = pattern.scan_all_shared(&data);
+        assert_eq!(&*matches, &[0x10, 0x7FF, 0x800, 0xF00]);
+
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let matches = Arc::clone(&matches);
+                std::thread::spawn(move || matches.iter().sum::<usize>())
+            })
+            .collect();
+
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), 0x10 + 0x7FF + 0x800 + 0xF00);
+        }
+    }
+}
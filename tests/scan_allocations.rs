@@ -0,0 +1,59 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use common::*;
+
+mod common;
+
+thread_local! {
+    /// Number of allocations performed by the current thread.
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Allocator that counts the allocations of each thread.
+struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+/// Tests that single-threaded scans do not allocate, so that the same pattern
+/// can be reused over many buffers without any per-scan cost.
+fn scan_allocations() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("55 48 89 E5 ? ? 48 8B")
+        .unwrap()
+        .with_exclusion(-1, 0xCC)
+        .build();
+
+    let buffers = (0..1000)
+        .map(|_| {
+            let mut data = random_bytes(0x400);
+            data[0x7F..0x88].copy_from_slice(b"\x90\x55\x48\x89\xE5\x00\x00\x48\x8B");
+            data
+        })
+        .collect::<Vec<_>>();
+
+    let before = ALLOCATIONS.with(Cell::get);
+    let mut matches = 0;
+    for data in buffers.iter() {
+        pattern.scan(data, |_| {
+            matches += 1;
+            true
+        });
+    }
+    let after = ALLOCATIONS.with(Cell::get);
+
+    assert!(matches >= 1000);
+    assert_eq!(after - before, 0);
+}
@@ -0,0 +1,19 @@
+#[test]
+/// Tests that the matches are reported relative to the image base.
+fn scan_based_addresses() {
+    let mut data = vec![0u8; 0x2000];
+    data[0x1234..0x1237].copy_from_slice(&[0x48, 0x8B, 0x05]);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    let mut addresses = vec![];
+    let found = pattern.scan_based(&data, 0x7FF6_0000_0000, |address| {
+        addresses.push(address);
+        true
+    });
+
+    assert!(found);
+    assert_eq!(addresses, vec![0x7FF6_0000_1234]);
+}
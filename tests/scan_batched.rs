@@ -0,0 +1,55 @@
+/// Scans the data in batches, and returns the batches.
+fn batches(pattern: &aobscan::Pattern, data: &[u8], batch: usize) -> Vec<Vec<usize>> {
+    let mut batches = vec![];
+    pattern.scan_batched(data, batch, |offsets| {
+        batches.push(offsets.to_vec());
+        true
+    });
+    batches
+}
+
+#[test]
+/// Tests that all the matches of dense data are delivered exactly once.
+fn scan_batched_dense() {
+    // Every position of the data is a match.
+    let data = vec![0xAAu8; 0x10000];
+
+    for threads in [1, 2, 3, 8] {
+        let pattern = aobscan::Pattern::new(vec![0xAA, 0xAA], vec![true, true], threads);
+        let expected = pattern.scan_all(&data);
+
+        for batch in [0, 1, 7, 0x100, 0x20000] {
+            let batches = batches(&pattern, &data, batch);
+            assert!(batches.iter().all(|offsets| !offsets.is_empty() && offsets.len() <= batch.max(1)));
+            assert!(batches.iter().all(|offsets| offsets.windows(2).all(|pair| pair[0] < pair[1])));
+
+            let mut offsets = batches.concat();
+            offsets.sort_unstable();
+            assert_eq!(offsets, expected);
+        }
+    }
+}
+
+#[test]
+/// Tests that the callback can stop the scan, and that the options are applied.
+fn scan_batched_stop() {
+    let data = vec![0xAAu8; 0x10000];
+
+    for threads in [1, 4] {
+        let pattern = aobscan::Pattern::new(vec![0xAA, 0xAA], vec![true, true], threads);
+        let mut calls = 0;
+        assert!(pattern.scan_batched(&data, 0x10, |_| {
+            calls += 1;
+            false
+        }));
+        assert_eq!(calls, 1);
+    }
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("AA AA")
+        .unwrap()
+        .with_overlapping(false)
+        .build();
+    assert_eq!(batches(&pattern, &data[..0x10], 3).concat(), vec![0, 2, 4, 6, 8, 10, 12, 14]);
+
+    assert!(!pattern.scan_batched(&[0u8; 0x10], 3, |_| true));
+}
@@ -0,0 +1,50 @@
+/// Collects the regions between the matches of the two patterns.
+fn regions(a: &str, b: &str, data: &[u8]) -> Vec<std::ops::Range<usize>> {
+    let a = aobscan::PatternBuilder::from_ida_style(a).unwrap().build();
+    let b = aobscan::PatternBuilder::from_ida_style(b).unwrap().build();
+
+    let mut regions = vec![];
+    aobscan::Pattern::scan_between(&a, &b, data, |range| {
+        regions.push(range);
+        true
+    });
+    regions
+}
+
+#[test]
+/// Tests that the regions between interleaved start and end markers are extracted.
+fn scan_between_interleaved() {
+    // AA BB = start marker, CC DD = end marker
+    let data = b"\xAA\xBBone\xCC\xDD--\xAA\xBBtwo!\xCC\xDD\xAA\xBB\xCC\xDD\xAA\xBBtail";
+
+    let ranges = regions("AA BB", "CC DD", data);
+    assert_eq!(ranges, vec![2..5, 11..15, 19..19]);
+    assert_eq!(&data[ranges[0].clone()], b"one");
+    assert_eq!(&data[ranges[1].clone()], b"two!");
+}
+
+#[test]
+/// Tests that the start markers without a following end marker are skipped.
+fn scan_between_unterminated() {
+    // The end marker before the first start marker is ignored, and the second
+    // start marker is inside the first region.
+    let data = b"\xCC\xDD\xAA\xBBx\xAA\xBBy\xCC\xDDz\xAA\xBB";
+    assert_eq!(regions("AA BB", "CC DD", data), vec![4..8]);
+
+    assert!(regions("AA BB", "CC DD", b"\xAA\xBB\x00\x00").is_empty());
+}
+
+#[test]
+/// Tests that the callback can stop the scan.
+fn scan_between_stop() {
+    let a = aobscan::PatternBuilder::from_ida_style("AA").unwrap().build();
+    let b = aobscan::PatternBuilder::from_ida_style("CC").unwrap().build();
+
+    let mut count = 0;
+    let found = aobscan::Pattern::scan_between(&a, &b, b"\xAA\xCC\xAA\xCC", |_| {
+        count += 1;
+        false
+    });
+    assert!(found);
+    assert_eq!(count, 1);
+}
@@ -0,0 +1,47 @@
+#[test]
+/// Tests that the matches of the pattern and of its mirror are reported with
+/// their orientation.
+fn scan_bidirectional_orientations() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x100];
+    data[0x10..0x14].copy_from_slice(&[0x48, 0x8B, 0x11, 0x05]);
+    data[0x80..0x84].copy_from_slice(&[0x05, 0x22, 0x8B, 0x48]);
+    // The mirror is not reported if it does not fit in the data.
+    data[0xFE..0x100].copy_from_slice(&[0x8B, 0x48]);
+
+    let mut matches = vec![];
+    let found = pattern.scan_bidirectional(&data, |offset, reversed| {
+        matches.push((offset, reversed));
+        true
+    });
+
+    assert!(found);
+    assert_eq!(matches, vec![(0x10, false), (0x80, true)]);
+}
+
+#[test]
+/// Tests that the palindromes are reported in both orientations, and that
+/// the scan can be stopped.
+fn scan_bidirectional_palindrome() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("CC 90 CC")
+        .unwrap()
+        .build();
+
+    let data = [0x00, 0xCC, 0x90, 0xCC, 0x00];
+    let mut matches = vec![];
+    pattern.scan_bidirectional(&data, |offset, reversed| {
+        matches.push((offset, reversed));
+        true
+    });
+    assert_eq!(matches, vec![(1, false), (1, true)]);
+
+    let mut calls = 0;
+    assert!(pattern.scan_bidirectional(&data, |_, _| {
+        calls += 1;
+        false
+    }));
+    assert_eq!(calls, 1);
+}
@@ -0,0 +1,69 @@
+/// Writes the given bits in the data, starting at the given bit offset. (MSB first)
+fn plant_bits(data: &mut [u8], offset: usize, bits: &[bool]) {
+    for (i, &bit) in bits.iter().enumerate() {
+        let position = offset + i;
+        let flag = 0x80 >> (position % 8);
+        if bit {
+            data[position / 8] |= flag;
+        } else {
+            data[position / 8] &= !flag;
+        }
+    }
+}
+
+/// Converts a string of `0`s and `1`s to bits.
+fn bits(pattern: &str) -> Vec<bool> {
+    pattern.chars().map(|c| c == '1').collect()
+}
+
+#[test]
+/// Tests that a bit pattern planted at a non-byte-aligned offset is found.
+fn scan_bits_unaligned() {
+    let pattern = bits("1011001110001111010");
+    let mask = vec![true; pattern.len()];
+
+    let mut data = vec![0u8; 0x40];
+    plant_bits(&mut data, 0x53, &pattern);
+
+    let mut offsets = vec![];
+    let found = aobscan::scan_bits(&data, &pattern, &mask, |offset| {
+        offsets.push(offset);
+        true
+    });
+
+    assert!(found);
+    assert_eq!(offsets, vec![0x53]);
+}
+
+#[test]
+/// Tests that the wildcard bits of the mask match any bit, and that
+/// matches ending on the last bit of the data are found.
+fn scan_bits_wildcards() {
+    let pattern = bits("1100011");
+    let mask = bits("1111011");
+
+    let mut data = vec![0u8; 0x10];
+    plant_bits(&mut data, 3, &bits("1100111"));
+    plant_bits(&mut data, 0x80 - 7, &pattern);
+
+    assert_eq!(
+        {
+            let mut offsets = vec![];
+            aobscan::scan_bits(&data, &pattern, &mask, |offset| {
+                offsets.push(offset);
+                true
+            });
+            offsets
+        },
+        vec![3, 0x80 - 7],
+    );
+}
+
+#[test]
+/// Tests that invalid bit patterns do not match.
+fn scan_bits_invalid() {
+    let data = vec![0xFFu8; 0x10];
+    assert!(!aobscan::scan_bits(&data, &[], &[], |_| true));
+    assert!(!aobscan::scan_bits(&data, &bits("11"), &bits("1"), |_| true));
+    assert!(!aobscan::scan_bits(&[0xFF], &bits("111111111"), &[true; 9], |_| true));
+}
@@ -0,0 +1,39 @@
+use aobscan::ScanError;
+
+/// Builds a buffer with a match of `48 8B` every 0x10 bytes.
+fn planted_data() -> Vec<u8> {
+    let mut data = vec![0u8; 0x1000];
+    for offset in (0..data.len()).step_by(0x10) {
+        data[offset..offset + 2].copy_from_slice(&[0x48, 0x8B]);
+    }
+    data
+}
+
+#[test]
+/// Tests that scans with at most the maximum number of matches succeed.
+fn scan_bounded_under_cap() {
+    let data = planted_data();
+
+    for threads in [1, 4] {
+        let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], threads);
+        let expected = pattern.scan_all(&data);
+        assert_eq!(expected.len(), 0x100);
+
+        assert_eq!(pattern.scan_bounded(&data, 0x100), Ok(expected.clone()));
+        assert_eq!(pattern.scan_bounded(&data, usize::MAX), Ok(expected));
+        assert_eq!(pattern.scan_bounded(&[0u8; 0x10], 0), Ok(vec![]));
+    }
+}
+
+#[test]
+/// Tests that scans with more than the maximum number of matches fail.
+fn scan_bounded_over_cap() {
+    let data = planted_data();
+
+    for threads in [1, 4] {
+        let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], threads);
+
+        assert_eq!(pattern.scan_bounded(&data, 0xFF), Err(ScanError::TooManyMatches { cap: 0xFF }));
+        assert_eq!(pattern.scan_bounded(&data, 0), Err(ScanError::TooManyMatches { cap: 0 }));
+    }
+}
@@ -0,0 +1,34 @@
+use std::time::Duration;
+
+#[test]
+/// Tests that a scan whose budget elapses returns a partial result.
+fn scan_budgeted_expired() {
+    // A pattern made of wildcards matches at every position, so the scan
+    // takes much longer than the budget.
+    let data = vec![0u8; 0x1000000];
+    let pattern = aobscan::PatternBuilder::from_ida_style("? 00")
+        .unwrap()
+        .build();
+
+    let (offsets, completed) = pattern.scan_budgeted(&data, Duration::from_millis(1));
+
+    assert!(!completed);
+    assert!(offsets.len() < data.len() - 1);
+}
+
+#[test]
+/// Tests that a scan that ends within the budget returns all the matches.
+fn scan_budgeted_completed() {
+    let mut data = vec![0u8; 0x1000];
+    data[0x10..0x12].copy_from_slice(&[0x48, 0x8B]);
+    data[0x800..0x802].copy_from_slice(&[0x48, 0x8B]);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build();
+
+    let (offsets, completed) = pattern.scan_budgeted(&data, Duration::from_secs(60));
+
+    assert!(completed);
+    assert_eq!(offsets, vec![0x10, 0x800]);
+}
@@ -0,0 +1,50 @@
+use aobscan::ScanBuffer;
+
+/// Builds a buffer with the given number of matches of `48 8B`.
+fn planted_data(matches: usize) -> Vec<u8> {
+    let mut data = vec![0u8; 0x100];
+    for offset in (0..matches * 4).step_by(4) {
+        data[offset..offset + 2].copy_from_slice(&[0x48, 0x8B]);
+    }
+    data
+}
+
+#[test]
+/// Tests that the buffer contains the same offsets as a collecting scan.
+fn scan_all_into_offsets() {
+    let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], 4);
+
+    let mut buf = ScanBuffer::new();
+    let data = planted_data(0x10);
+    assert!(pattern.scan_all_into(&data, &mut buf));
+    assert_eq!(buf.offsets(), pattern.scan_all(&data).as_slice());
+
+    // The previous offsets are discarded.
+    assert!(!pattern.scan_all_into(&[0u8; 0x10], &mut buf));
+    assert!(buf.offsets().is_empty());
+}
+
+#[test]
+/// Tests that repeated scans into the same buffer reuse its capacity.
+fn scan_all_into_reuses_capacity() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build();
+
+    let mut buf = ScanBuffer::with_capacity(0x20);
+    let capacity = buf.capacity();
+    for matches in [0x20, 0x08, 0x00, 0x1F, 0x20, 0x10] {
+        pattern.scan_all_into(&planted_data(matches), &mut buf);
+        assert_eq!(buf.offsets().len(), matches);
+        assert_eq!(buf.capacity(), capacity);
+    }
+
+    // The capacity only grows when a scan finds more matches than the previous ones.
+    let mut buf = ScanBuffer::new();
+    pattern.scan_all_into(&planted_data(0x30), &mut buf);
+    let capacity = buf.capacity();
+    for matches in [0x01, 0x30, 0x2F, 0x10] {
+        pattern.scan_all_into(&planted_data(matches), &mut buf);
+        assert_eq!(buf.capacity(), capacity);
+    }
+}
@@ -0,0 +1,39 @@
+#[test]
+/// Tests that the scan stops when the callback calls `ScanControl::stop`.
+fn scan_control_stop() {
+    let data = b"\x90\x48\x8B\x90\x48\x8B\x90\x48\x8B\x90";
+
+    let mut offsets = vec![];
+    let result = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build()
+        .scan_control(data, |offset, control| {
+            offsets.push(offset);
+            control.stop();
+        });
+
+    assert!(result);
+    assert_eq!(offsets, vec![1]);
+}
+
+#[test]
+/// Tests that `ScanControl::skip_ahead` moves the scan cursor past overlapping matches.
+fn scan_control_skip_ahead() {
+    let data = b"\xAA\xAA\xAA\xAA\xAA\xAA\xAA\x90";
+    let pattern = aobscan::PatternBuilder::from_ida_style("AA AA")
+        .unwrap()
+        .build();
+
+    // Without skipping, every position is reported.
+    let mut offsets = vec![];
+    pattern.scan_control(data, |offset, _| offsets.push(offset));
+    assert_eq!(offsets, vec![0, 1, 2, 3, 4, 5]);
+
+    // Skipping the length of the pattern minus one avoids overlapping hits.
+    offsets.clear();
+    pattern.scan_control(data, |offset, control| {
+        offsets.push(offset);
+        control.skip_ahead(1);
+    });
+    assert_eq!(offsets, vec![0, 2, 4]);
+}
@@ -0,0 +1,27 @@
+use std::borrow::Cow;
+
+#[test]
+/// Tests that both owned and borrowed data behind a `Cow` can be scanned.
+fn scan_cow() {
+    let mut bytes = vec![0u8; 0x1000];
+    bytes[0x321..0x324].copy_from_slice(&[0x48, 0x8B, 0x05]);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    let borrowed: Cow<[u8]> = Cow::Borrowed(&bytes);
+    let owned: Cow<[u8]> = Cow::Owned(bytes.clone());
+
+    for data in [&borrowed, &owned] {
+        let mut offsets = vec![];
+        assert!(pattern.scan(data, |offset| {
+            offsets.push(offset);
+            true
+        }));
+        assert_eq!(offsets, vec![0x321]);
+    }
+
+    // Owned data can also be moved into the scan.
+    assert!(pattern.scan(owned, |_| true));
+}
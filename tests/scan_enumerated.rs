@@ -0,0 +1,53 @@
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that the ordinals of a complete scan are a contiguous set with no duplicates,
+/// with any number of threads.
+fn scan_enumerated_contiguous() {
+    let mut data = random_bytes_seeded(0x10000, 0x0B5CA4);
+    let planted = [0x100, 0x2345, 0x8000, 0xFFF0];
+    for &offset in &planted {
+        data[offset..offset + 4].copy_from_slice(&[0x48, 0x8B, 0x05, 0x90]);
+    }
+
+    for threads in [1, 4] {
+        let pattern = aobscan::Pattern::new(vec![0x48, 0x8B, 0x05, 0x90], vec![true; 4], threads);
+
+        let mut entries = vec![];
+        let found = pattern.scan_enumerated(&data, |ordinal, offset| {
+            entries.push((ordinal, offset));
+            true
+        });
+        assert!(found);
+
+        let mut ordinals: Vec<usize> = entries.iter().map(|&(ordinal, _)| ordinal).collect();
+        ordinals.sort_unstable();
+        assert_eq!(ordinals, (0..entries.len()).collect::<Vec<_>>());
+
+        let mut offsets: Vec<usize> = entries.iter().map(|&(_, offset)| offset).collect();
+        offsets.sort_unstable();
+        assert_eq!(offsets, pattern.scan_all(&data));
+    }
+}
+
+#[test]
+/// Tests that the ordinals follow the offsets in single-threaded scans, and that
+/// the scan can be stopped at the Nth match.
+fn scan_enumerated_stop() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("CC")
+        .unwrap()
+        .build();
+
+    let data = [0xCC, 0x90, 0xCC, 0xCC, 0x90, 0xCC];
+    let mut third = None;
+    pattern.scan_enumerated(&data, |ordinal, offset| {
+        if ordinal == 2 {
+            third = Some(offset);
+            return false;
+        }
+        true
+    });
+    assert_eq!(third, Some(3));
+}
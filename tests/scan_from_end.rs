@@ -0,0 +1,45 @@
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that a needle planted near the end of the data is reported with its
+/// distance from the end.
+fn scan_from_end_trailer() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? 50 4B 05 06")
+        .unwrap()
+        .build();
+
+    let mut data = random_bytes_seeded(0x1000, 0x996);
+    data[0xFE0..0xFE5].copy_from_slice(&[0x00, 0x50, 0x4B, 0x05, 0x06]);
+    data[0xFFB..0x1000].copy_from_slice(&[0x00, 0x50, 0x4B, 0x05, 0x06]);
+
+    let mut distances = vec![];
+    let found = pattern.scan_from_end(&data, |distance| {
+        distances.push(distance);
+        true
+    });
+
+    assert!(found);
+    assert_eq!(distances, vec![0x1000 - 0xFE5, 0]);
+    for &distance in &distances {
+        let offset = data.len() - distance - pattern.len();
+        assert!(pattern.matches_at(&data, offset));
+    }
+}
+
+#[test]
+/// Tests that the matches that omit the optional bytes at the end of the data are
+/// reported as ending with it.
+fn scan_from_end_optional() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("50 4B (05)")
+        .unwrap()
+        .build();
+
+    let mut distances = vec![];
+    pattern.scan_from_end(&[0x50, 0x4B, 0x05, 0x00, 0x50, 0x4B], |distance| {
+        distances.push(distance);
+        true
+    });
+    assert_eq!(distances, vec![3, 0]);
+}
@@ -0,0 +1,58 @@
+/// Collects the matches of a fuzzy scan as (offset, mismatches) pairs.
+fn fuzzy_matches(pattern: &aobscan::Pattern, data: &[u8], max_mismatches: usize) -> Vec<(usize, usize)> {
+    let mut matches = vec![];
+    pattern.scan_fuzzy(data, max_mismatches, |offset, mismatches| {
+        matches.push((offset, mismatches));
+        true
+    });
+    matches
+}
+
+/// Builds a buffer with an exact match at 0x100, a match with a single mismatch
+/// at 0x200, and a match with three mismatches at 0x300.
+fn planted_data() -> Vec<u8> {
+    let mut data = vec![0u8; 0x400];
+    data[0x100..0x106].copy_from_slice(&[0x48, 0x8B, 0x05, 0xAA, 0x11, 0x22]);
+    data[0x200..0x206].copy_from_slice(&[0x48, 0x8B, 0x06, 0xBB, 0x11, 0x22]);
+    data[0x300..0x306].copy_from_slice(&[0x48, 0x8C, 0x06, 0xCC, 0x11, 0x23]);
+    data
+}
+
+#[test]
+/// Tests that a fuzzy scan without mismatches only reports the exact matches.
+fn scan_fuzzy_exact() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05 ? 11 22")
+        .unwrap()
+        .build();
+
+    assert_eq!(fuzzy_matches(&pattern, &planted_data(), 0), vec![(0x100, 0)]);
+}
+
+#[test]
+/// Tests that a fuzzy scan reports the positions with a single mismatched byte,
+/// and that wildcards never count as mismatches.
+fn scan_fuzzy_one_off() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05 ? 11 22")
+        .unwrap()
+        .build();
+
+    assert_eq!(
+        fuzzy_matches(&pattern, &planted_data(), 1),
+        vec![(0x100, 0), (0x200, 1)]
+    );
+}
+
+#[test]
+/// Tests that a fuzzy scan does not report positions with too many mismatches.
+fn scan_fuzzy_too_many_mismatches() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05 ? 11 22")
+        .unwrap()
+        .build();
+
+    let matches = fuzzy_matches(&pattern, &planted_data(), 2);
+    assert!(matches.contains(&(0x200, 1)));
+    assert!(!matches.iter().any(|&(offset, _)| offset == 0x300));
+    assert!(matches.iter().all(|&(_, mismatches)| mismatches <= 2));
+
+    assert!(fuzzy_matches(&pattern, &planted_data(), 3).contains(&(0x300, 3)));
+}
@@ -0,0 +1,67 @@
+fn scan_indel(pattern: &aobscan::Pattern, data: &[u8]) -> Vec<(usize, isize)> {
+    let mut matches = vec![];
+    pattern.scan_indel(data, |offset, difference| {
+        matches.push((offset, difference));
+        true
+    });
+    matches
+}
+
+#[test]
+/// Tests that the exact matches, the matches with an inserted byte and the
+/// matches with a deleted byte are all found.
+fn scan_indel_variants() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05 ? 90 C3")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x60];
+    data[0x10..0x16].copy_from_slice(&[0x48, 0x8B, 0x05, 0x11, 0x90, 0xC3]);
+    data[0x20..0x27].copy_from_slice(&[0x48, 0x8B, 0xEE, 0x05, 0x11, 0x90, 0xC3]);
+    data[0x30..0x35].copy_from_slice(&[0x48, 0x8B, 0x05, 0x90, 0xC3]);
+    data[0x40..0x45].copy_from_slice(&[0x48, 0x05, 0x11, 0x90, 0xC3]);
+
+    assert_eq!(
+        scan_indel(&pattern, &data),
+        vec![(0x10, 0), (0x20, 1), (0x30, -1), (0x40, -1)],
+    );
+}
+
+#[test]
+/// Tests that two edits are not tolerated, and that the edited matches must fit in the data.
+fn scan_indel_limits() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05 90")
+        .unwrap()
+        .build();
+
+    // Two inserted bytes, two deleted bytes, and a substitution.
+    let data = [0x48, 0xEE, 0x8B, 0xEE, 0x05, 0x90, 0x00, 0x48, 0x90, 0x00, 0x48, 0x8B, 0xEE, 0x90];
+    assert!(scan_indel(&pattern, &data).is_empty());
+
+    // The inserted byte is at the end of the data.
+    let data = [0x48, 0x8B, 0xEE, 0x05];
+    assert!(scan_indel(&pattern, &data).is_empty());
+
+    // The deleted byte leaves a match that ends with the data.
+    let data = [0x00, 0x48, 0x8B, 0x90];
+    assert_eq!(scan_indel(&pattern, &data), vec![(1, -1)]);
+}
+
+#[test]
+/// Tests that the offsets of the matches include the leading wildcards, and that the
+/// scan can be stopped.
+fn scan_indel_leading_wildcards() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? ? 48 8B 05")
+        .unwrap()
+        .build();
+
+    let data = [0x00, 0x00, 0x48, 0x05, 0x00, 0x00, 0x48, 0x8B, 0x05];
+    assert_eq!(scan_indel(&pattern, &data), vec![(0, -1), (4, 0)]);
+
+    let mut calls = 0;
+    assert!(pattern.scan_indel(&data, |_, _| {
+        calls += 1;
+        false
+    }));
+    assert_eq!(calls, 1);
+}
@@ -0,0 +1,74 @@
+#[test]
+/// Tests that a match contained in a single buffer is reported in the
+/// coordinates of the concatenated buffers.
+fn scan_iovecs_inside_buffer() {
+    let first = [0u8; 0x10];
+    let second = b"\x90\x90\x55\x48\x89\xE5\x90\x90";
+
+    let mut offsets = vec![];
+    let result = aobscan::PatternBuilder::from_ida_style("55 48 89 E5")
+        .unwrap()
+        .build()
+        .scan_iovecs(&[&first, second], |offset| {
+            offsets.push(offset);
+            true
+        });
+
+    assert!(result);
+    assert_eq!(offsets, vec![0x12]);
+}
+
+#[test]
+/// Tests that matches spanning the seams between buffers are reported once.
+fn scan_iovecs_across_seams() {
+    let first = b"\x90\x90\x55\x48";
+    let second = b"\x89";
+    let third = b"\xE5\x90\x55";
+    let fourth = b"\x48\x89\xE5";
+
+    let mut offsets = vec![];
+    let result = aobscan::PatternBuilder::from_ida_style("55 48 89 E5")
+        .unwrap()
+        .build()
+        .scan_iovecs(&[first, second, &[], third, fourth], |offset| {
+            offsets.push(offset);
+            true
+        });
+
+    assert!(result);
+    assert_eq!(offsets, vec![2, 7]);
+
+    // The concatenated buffers give the same result.
+    let concatenated = [first.as_slice(), second, third, fourth].concat();
+    let expected = aobscan::PatternBuilder::from_ida_style("55 48 89 E5")
+        .unwrap()
+        .build()
+        .scan_all(&concatenated);
+    assert_eq!(offsets, expected);
+}
+
+#[test]
+/// Tests that the matches that omit optional bytes at the end of a buffer are
+/// not reported again by the scan of the seam.
+fn scan_iovecs_optional_bytes() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("55 (48) 89")
+        .unwrap()
+        .build();
+
+    let mut offsets = vec![];
+    pattern.scan_iovecs(&[b"\x90\x55\x89", b"\x90\x90"], |offset| {
+        offsets.push(offset);
+        true
+    });
+    assert_eq!(offsets, vec![1]);
+
+    // The matches that contain or omit the optional byte across the seams are still reported.
+    let bufs: [&[u8]; 4] = [b"\x90\x55", b"\x48\x89\x55", b"\x89", b"\x90"];
+    let mut offsets = vec![];
+    pattern.scan_iovecs(&bufs, |offset| {
+        offsets.push(offset);
+        true
+    });
+    assert_eq!(offsets, pattern.scan_all(&bufs.concat()));
+    assert_eq!(offsets, vec![1, 4]);
+}
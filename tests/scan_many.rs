@@ -0,0 +1,51 @@
+#![cfg(feature = "rayon")]
+
+use std::sync::Mutex;
+
+#[test]
+/// Tests that the matches are attributed to the buffer that contains them.
+fn scan_many_attribution() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 05")
+        .unwrap()
+        .build();
+
+    let mut buffers = vec![vec![0u8; 0x100]; 4];
+    buffers[0][0x10..0x14].copy_from_slice(&[0x48, 0x8B, 0x00, 0x05]);
+    buffers[2][0x00..0x04].copy_from_slice(&[0x48, 0x8B, 0x01, 0x05]);
+    buffers[2][0xFC..0x100].copy_from_slice(&[0x48, 0x8B, 0x02, 0x05]);
+    // A match split between two buffers is not a match.
+    buffers[3][0xFE..0x100].copy_from_slice(&[0x48, 0x8B]);
+
+    let slices: Vec<&[u8]> = buffers.iter().map(Vec::as_slice).collect();
+    let hits = Mutex::new(vec![]);
+    let found = pattern.scan_many(slices, |index, offset| {
+        hits.lock().unwrap().push((index, offset));
+        true
+    });
+
+    let mut hits = hits.into_inner().unwrap();
+    hits.sort_unstable();
+    assert!(found);
+    assert_eq!(hits, vec![(0, 0x10), (2, 0x00), (2, 0xFC)]);
+}
+
+#[test]
+/// Tests that returning `false` from the callback stops the scan of all the buffers.
+fn scan_many_stop() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("CC")
+        .unwrap()
+        .build();
+
+    let buffers = vec![vec![0xCCu8; 0x100]; 8];
+    let slices: Vec<&[u8]> = buffers.iter().map(Vec::as_slice).collect();
+
+    let mut calls = 0;
+    assert!(pattern.scan_many(slices, |_, _| {
+        calls += 1;
+        false
+    }));
+    assert_eq!(calls, 1);
+
+    let empty: Vec<&[u8]> = vec![];
+    assert!(!pattern.scan_many(empty, |_, _| true));
+}
@@ -0,0 +1,51 @@
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that the collected matches contain the correct span and bytes.
+fn scan_matches() {
+    let mut data = random_bytes(1024 * 64 /* 64 KB */);
+
+    // Plant the needle at a few known offsets.
+    let needle = b"\x55\x48\x89\xE5\x48\x8B\x05";
+    let targets = [0x100, 0x2000, 0x8000];
+    for &target in &targets {
+        data[target..target + needle.len()].copy_from_slice(needle);
+    }
+
+    let matches = aobscan::PatternBuilder::from_ida_style("55 48 89 ? 48 8B 05")
+        .unwrap()
+        .build()
+        .scan_matches(&data);
+
+    assert_eq!(
+        matches.iter().map(|m| m.offset).collect::<Vec<_>>(),
+        targets.to_vec()
+    );
+
+    for m in matches.iter() {
+        assert_eq!(m.end, m.offset + needle.len());
+        assert_eq!(m.bytes, needle.to_vec());
+    }
+}
+
+#[test]
+/// Tests that the span and bytes of the matches of a variable-length pattern are
+/// those of each match, whether it contains the optional bytes or not.
+fn scan_matches_variable_length() {
+    let mut data = vec![0u8; 0x100];
+    data[0x10..0x14].copy_from_slice(b"\x55\x48\x89\xE5");
+    data[0x40..0x43].copy_from_slice(b"\x55\x89\xE5");
+
+    let matches = aobscan::PatternBuilder::from_ida_style("55 (48) 89 E5")
+        .unwrap()
+        .build()
+        .scan_matches(&data);
+
+    assert_eq!(matches.len(), 2);
+    assert_eq!((matches[0].offset, matches[0].end), (0x10, 0x14));
+    assert_eq!(matches[0].bytes, b"\x55\x48\x89\xE5".to_vec());
+    assert_eq!((matches[1].offset, matches[1].end), (0x40, 0x43));
+    assert_eq!(matches[1].bytes, b"\x55\x89\xE5".to_vec());
+}
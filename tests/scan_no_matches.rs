@@ -11,7 +11,7 @@ fn scan_no_matches() {
         .unwrap()
         .with_all_threads()
         .build()
-        .scan(&empty_data, |offset| {
+        .scan(empty_data.as_slice(), |offset| {
             println!("Found match at offset 0x{:X}", offset);
             called = true;
             true
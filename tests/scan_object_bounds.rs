@@ -0,0 +1,57 @@
+#![cfg(feature = "object-scan")]
+
+use object::{BinaryFormat, SectionKind};
+
+use aobscan::ObjectScan;
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that a match ending with the last byte of a section is found.
+fn scan_object_needle_at_section_end() {
+    let mut text = vec![0x90u8; 0x40];
+    text.extend_from_slice(b"\x55\x48\x89\xE5");
+    let elf = build_object(
+        BinaryFormat::Elf,
+        &[(".text", SectionKind::Text, &text), (".data", SectionKind::Data, &[0u8; 0x10])],
+    );
+
+    let mut results = vec![];
+    let result = aobscan::PatternBuilder::from_ida_style("55 48 89 E5")
+        .unwrap()
+        .build()
+        .scan_object(&elf, ".text", |result| {
+            results.push(result);
+            true
+        });
+
+    assert_eq!(result, Ok(true));
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].section_offset, 0x40);
+    assert_eq!(&elf[results[0].raw_offset..results[0].raw_offset + 4], b"\x55\x48\x89\xE5");
+}
+
+#[test]
+/// Tests that a match straddling the end of a section is not reported.
+fn scan_object_needle_straddling_section_end() {
+    let mut text = vec![0x90u8; 0x40];
+    text.extend_from_slice(b"\x55\x48");
+    let mut data = b"\x89\xE5".to_vec();
+    data.extend_from_slice(&[0u8; 0x10]);
+    let elf = build_object(
+        BinaryFormat::Elf,
+        &[(".text", SectionKind::Text, &text), (".data", SectionKind::Data, &data)],
+    );
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("55 48 89 E5")
+        .unwrap()
+        .build();
+
+    // The needle is contiguous in the file, across the two sections.
+    assert!(pattern.scan(&elf, |_| true));
+
+    // But it is not contained in any of the sections.
+    assert_eq!(pattern.scan_object(&elf, ".text", |_| true), Ok(false));
+    assert_eq!(pattern.scan_object(&elf, ".data", |_| true), Ok(false));
+}
@@ -0,0 +1,229 @@
+#![cfg(feature = "object-scan")]
+
+use std::sync::Mutex;
+
+use object::{Architecture, BinaryFormat, SectionKind};
+use object::macho::{CPU_TYPE_ARM64, CPU_TYPE_X86_64};
+
+use aobscan::{ArchKey, ObjectScan, SectionResult};
+use common::*;
+
+mod common;
+
+const NEEDLE: &[u8] = &[0x48, 0x8B, 0x05, 0x90];
+
+/// Builds the key of a slice of a FAT archive built with [`build_fat`].
+fn slice_key(slice: usize, architecture: Architecture) -> ArchKey {
+    ArchKey {
+        slice: Some(slice),
+        architecture,
+        cpu_subtype: Some(0),
+    }
+}
+
+/// Builds a FAT archive with an x86-64 and an arm64 slice, each containing the
+/// needle in its `__text` section at the given offsets.
+fn build_universal(x86_offsets: &[usize], arm_offsets: &[usize]) -> Vec<u8> {
+    let plant = |offsets: &[usize]| {
+        let mut text = vec![0u8; 0x400];
+        for &offset in offsets {
+            text[offset..offset + NEEDLE.len()].copy_from_slice(NEEDLE);
+        }
+        text
+    };
+
+    let x86 = build_object_for(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        &[("__text", SectionKind::Text, &plant(x86_offsets))],
+    );
+    let arm = build_object_for(
+        BinaryFormat::MachO,
+        Architecture::Aarch64,
+        &[("__text", SectionKind::Text, &plant(arm_offsets))],
+    );
+
+    build_fat(&[(CPU_TYPE_X86_64, &x86), (CPU_TYPE_ARM64, &arm)])
+}
+
+#[test]
+/// Tests that the matches of all the slices of a FAT archive are reported,
+/// with the correct offsets and architecture.
+fn scan_object_fat_all_slices() {
+    let data = build_universal(&[0x10, 0x200], &[0x80]);
+    let pattern = aobscan::PatternBuilder::from_hex_string("488b0590")
+        .unwrap()
+        .build();
+
+    let results = Mutex::new(Vec::<SectionResult>::new());
+    let found = pattern.scan_object(&data, "__text", |result| {
+        results.lock().unwrap().push(result);
+        true
+    }).unwrap();
+
+    assert!(found);
+
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|result| result.raw_offset);
+
+    let summary = results.iter()
+        .map(|result| (result.archive_id.as_deref().unwrap(), result.section_offset))
+        .collect::<Vec<_>>();
+    assert_eq!(summary, vec![("X86_64", 0x10), ("X86_64", 0x200), ("Aarch64", 0x80)]);
+
+    for result in &results {
+        assert_eq!(&data[result.raw_offset..result.raw_offset + NEEDLE.len()], NEEDLE);
+    }
+}
+
+#[test]
+/// Tests that stopping the scan in the callback also stops the scan of the
+/// other slices of a FAT archive, with both single and multi-threaded patterns.
+fn scan_object_fat_stop() {
+    let data = build_universal(&[0x10, 0x200], &[0x80, 0x300]);
+
+    for threads in 1..=4 {
+        let pattern = aobscan::Pattern::new(NEEDLE.to_vec(), vec![true; NEEDLE.len()], threads);
+
+        let mut calls = 0;
+        let found = pattern.scan_object(&data, "__text", |_| {
+            calls += 1;
+            false
+        }).unwrap();
+
+        assert!(found);
+        assert_eq!(calls, 1, "threads: {}", threads);
+    }
+}
+
+#[test]
+/// Tests that the matches of a FAT archive are grouped by architecture, and that
+/// the architectures without the section are omitted.
+fn scan_object_by_arch() {
+    let pattern = aobscan::PatternBuilder::from_hex_string("488b0590")
+        .unwrap()
+        .build();
+
+    let data = build_universal(&[0x10, 0x200], &[]);
+    let results = pattern.scan_object_by_arch(&data, "__text").unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(
+        results[&slice_key(0, Architecture::X86_64)].iter().map(|result| result.section_offset).collect::<Vec<_>>(),
+        vec![0x10, 0x200]
+    );
+    assert!(results[&slice_key(1, Architecture::Aarch64)].is_empty());
+
+    // Slices without the section are omitted.
+    let text = build_object_for(
+        BinaryFormat::MachO,
+        Architecture::X86_64,
+        &[("__text", SectionKind::Text, NEEDLE)],
+    );
+    let data_only = build_object_for(
+        BinaryFormat::MachO,
+        Architecture::Aarch64,
+        &[("__data", SectionKind::Data, NEEDLE)],
+    );
+    let data = build_fat(&[(CPU_TYPE_X86_64, &text), (CPU_TYPE_ARM64, &data_only)]);
+    let results = pattern.scan_object_by_arch(&data, "__text").unwrap();
+
+    assert_eq!(results.keys().collect::<Vec<_>>(), vec![&slice_key(0, Architecture::X86_64)]);
+    assert_eq!(results[&slice_key(0, Architecture::X86_64)][0].section_offset, 0);
+
+    assert_eq!(
+        pattern.scan_object_by_arch(&data, "__const"),
+        Err(aobscan::ObjectError::SectionNotFound)
+    );
+}
+
+#[test]
+/// Tests that the raw offset of the matches locates the matched bytes in the
+/// original data, for both FAT slices and THIN binaries.
+fn scan_object_raw_offset() {
+    let pattern = aobscan::PatternBuilder::from_hex_string("488b0590")
+        .unwrap()
+        .build();
+
+    let fat = build_universal(&[0x30], &[0x1F0]);
+    let mut text = vec![0u8; 0x100];
+    text[0x40..0x44].copy_from_slice(NEEDLE);
+    let thin = build_object_for(
+        BinaryFormat::Elf,
+        Architecture::X86_64,
+        &[("__text", SectionKind::Text, &text)],
+    );
+
+    for data in [fat, thin] {
+        let offsets = Mutex::new(vec![]);
+        pattern.scan_object(&data, "__text", |result| {
+            offsets.lock().unwrap().push(result.raw_offset);
+            true
+        }).unwrap();
+
+        let offsets = offsets.into_inner().unwrap();
+        assert!(!offsets.is_empty());
+        for offset in offsets {
+            assert_eq!(&data[offset..offset + pattern.len()], NEEDLE);
+        }
+    }
+}
+
+#[test]
+/// Tests that the matches with the same bytes in different slices are collapsed
+/// into the first one.
+fn dedup_by_content_fat() {
+    let mut data = build_universal(&[0x10], &[0x80, 0x200]);
+    let pattern = aobscan::PatternBuilder::from_hex_string("488b05")
+        .unwrap()
+        .build();
+
+    let mut results = pattern.scan_object_by_arch(&data, "__text").unwrap()
+        .into_values()
+        .flatten()
+        .collect::<Vec<_>>();
+    results.sort_by_key(|result| result.raw_offset);
+
+    // All the matches are followed by the same byte.
+    let deduped = aobscan::dedup_by_content(&data, &results, NEEDLE.len());
+    assert_eq!(deduped, vec![results[0].clone()]);
+
+    // Change the byte after the last match, so that it is distinct.
+    data[results[2].raw_offset + 3] = 0xC3;
+    let deduped = aobscan::dedup_by_content(&data, &results, NEEDLE.len());
+    assert_eq!(deduped, vec![results[0].clone(), results[2].clone()]);
+
+    // The matches whose bytes do not fit in the data are kept.
+    let deduped = aobscan::dedup_by_content(&data, &results, data.len());
+    assert_eq!(deduped, results);
+}
+
+#[test]
+/// Tests that the slices with the same architecture are collected separately, and
+/// that normal binaries are identified by their architecture alone.
+fn scan_object_by_arch_same_architecture() {
+    let pattern = aobscan::PatternBuilder::from_hex_string("488b0590")
+        .unwrap()
+        .build();
+
+    let plant = |offset: usize| {
+        let mut text = vec![0u8; 0x100];
+        text[offset..offset + NEEDLE.len()].copy_from_slice(NEEDLE);
+        build_object_for(BinaryFormat::MachO, Architecture::Aarch64, &[("__text", SectionKind::Text, &text)])
+    };
+    let (first, second) = (plant(0x10), plant(0x20));
+    let data = build_fat(&[(CPU_TYPE_ARM64, &first), (CPU_TYPE_ARM64, &second)]);
+
+    let results = pattern.scan_object_by_arch(&data, "__text").unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[&slice_key(0, Architecture::Aarch64)][0].section_offset, 0x10);
+    assert_eq!(results[&slice_key(1, Architecture::Aarch64)][0].section_offset, 0x20);
+
+    let results = pattern.scan_object_by_arch(&first, "__text").unwrap();
+    let key = ArchKey {
+        slice: None,
+        architecture: Architecture::Aarch64,
+        cpu_subtype: None,
+    };
+    assert_eq!(results.keys().collect::<Vec<_>>(), vec![&key]);
+}
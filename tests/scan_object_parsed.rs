@@ -0,0 +1,52 @@
+#![cfg(feature = "object-scan")]
+
+use object::{BinaryFormat, SectionKind};
+
+use aobscan::ObjectScan;
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that a parsed object file can be scanned for multiple patterns.
+fn scan_object_parsed() {
+    let text = b"\x55\x48\x89\xE5\x48\x83\xEC\x10\xC9\xC3";
+    let elf = build_object(BinaryFormat::Elf, &[(".text", SectionKind::Text, text)]);
+    let file = object::File::parse(elf.as_slice()).unwrap();
+
+    let mut prologue = vec![];
+    let result = aobscan::PatternBuilder::from_ida_style("55 48 89 E5")
+        .unwrap()
+        .build()
+        .scan_parsed(&file, ".text", |result| {
+            prologue.push(result.section_offset);
+            true
+        });
+    assert_eq!(result, Ok(true));
+    assert_eq!(prologue, vec![0]);
+
+    let mut epilogue = vec![];
+    let result = aobscan::PatternBuilder::from_ida_style("C9 C3")
+        .unwrap()
+        .build()
+        .scan_parsed(&file, ".text", |result| {
+            epilogue.push(result.section_offset);
+            true
+        });
+    assert_eq!(result, Ok(true));
+    assert_eq!(epilogue, vec![8]);
+
+    // The results must be the same as with the byte slice entry point.
+    let pattern = aobscan::PatternBuilder::from_ida_style("C9 C3").unwrap().build();
+    let mut raw_parsed = vec![];
+    let mut raw_sliced = vec![];
+    pattern.scan_parsed(&file, ".text", |result| {
+        raw_parsed.push(result);
+        true
+    }).unwrap();
+    pattern.scan_object(&elf, ".text", |result| {
+        raw_sliced.push(result);
+        true
+    }).unwrap();
+    assert_eq!(raw_parsed, raw_sliced);
+}
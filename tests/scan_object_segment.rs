@@ -0,0 +1,56 @@
+#![cfg(feature = "object-scan")]
+
+use object::{BinaryFormat, SectionKind};
+
+use aobscan::ObjectScan;
+use common::*;
+
+mod common;
+
+const NEEDLE: &[u8] = &[0x48, 0x8B, 0x05, 0x90];
+
+#[test]
+/// Tests that the matches in Mach-O binaries report the segment of their section.
+fn scan_object_segment_macho() {
+    let macho = build_object(
+        BinaryFormat::MachO,
+        &[
+            ("__text", SectionKind::Text, NEEDLE),
+            ("__data", SectionKind::Data, NEEDLE),
+        ],
+    );
+
+    let pattern = aobscan::PatternBuilder::from_hex_string("488b0590")
+        .unwrap()
+        .build();
+
+    for (section, segment) in [("__text", "__TEXT"), ("__data", "__DATA")] {
+        let mut segments = vec![];
+        pattern.scan_object(&macho, section, |result| {
+            segments.push(result.segment_name);
+            true
+        }).unwrap();
+
+        assert_eq!(segments, vec![Some(segment.to_string())]);
+    }
+}
+
+#[test]
+/// Tests that the matches in formats without segments do not report a segment.
+fn scan_object_segment_none() {
+    let pattern = aobscan::PatternBuilder::from_hex_string("488b0590")
+        .unwrap()
+        .build();
+
+    for format in [BinaryFormat::Elf, BinaryFormat::Coff] {
+        let data = build_object(format, &[(".text", SectionKind::Text, NEEDLE)]);
+
+        let mut segments = vec![];
+        pattern.scan_object(&data, ".text", |result| {
+            segments.push(result.segment_name);
+            true
+        }).unwrap();
+
+        assert_eq!(segments, vec![None], "{:?}", format);
+    }
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "object-scan")]
+
+use aobscan::{ObjectError, ObjectScan};
+
+/// Minimal WebAssembly module with a single function, whose body is:
+/// `i32.const 42; drop; i32.const 7; drop; end`
+static WASM_MODULE: &[u8] = include_bytes!("fixtures/planted.wasm");
+
+#[test]
+/// Tests that a byte sequence can be found in the code section of a WebAssembly module.
+fn scan_object_wasm() {
+    // i32.const 42 (...) drop; i32.const 7
+    let needle = b"\x2A\x1A\x41\x07";
+
+    let mut results = vec![];
+    let result = aobscan::PatternBuilder::from_ida_style("2A 1A 41 07")
+        .unwrap()
+        .build()
+        .scan_object(WASM_MODULE, "code", |result| {
+            results.push(result);
+            true
+        });
+
+    assert_eq!(result, Ok(true));
+    assert_eq!(results.len(), 1);
+
+    // The raw offset must point to the needle in the module.
+    let raw_offset = results[0].raw_offset;
+    assert_eq!(&WASM_MODULE[raw_offset..raw_offset + needle.len()], needle);
+
+    // Function count + body size + local count + i32.const opcode.
+    assert_eq!(results[0].section_offset, 4);
+    assert_eq!(results[0].section_address, 0);
+
+    // The name used by the object crate must work as well.
+    assert_eq!(
+        aobscan::PatternBuilder::from_ida_style("2A 1A 41 07")
+            .unwrap()
+            .build()
+            .scan_object(WASM_MODULE, "<code>", |_| true),
+        Ok(true)
+    );
+}
+
+#[test]
+/// Tests that scanning a section that does not exist in a WebAssembly module fails.
+fn scan_object_wasm_unknown_section() {
+    let result = aobscan::PatternBuilder::from_ida_style("2A 1A 41 07")
+        .unwrap()
+        .build()
+        .scan_object(WASM_MODULE, "__text", |_| true);
+
+    assert_eq!(result, Err(ObjectError::SectionNotFound));
+}
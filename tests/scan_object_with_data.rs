@@ -0,0 +1,37 @@
+#![cfg(feature = "object-scan")]
+
+use object::{BinaryFormat, SectionKind};
+
+use aobscan::ObjectScan;
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that the callback receives the data of the section, and that the
+/// section offset of the match indexes it correctly.
+fn scan_object_with_data() {
+    let mut text = vec![0xCCu8; 0x200];
+    text[0x123..0x127].copy_from_slice(&[0x48, 0x8B, 0x05, 0x90]);
+    let elf = build_object(BinaryFormat::Elf, &[
+        (".data", SectionKind::Data, &[0u8; 0x40]),
+        (".text", SectionKind::Text, &text),
+    ]);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    let mut calls = 0;
+    let result = pattern.scan_object_with_data(&elf, ".text", |result, section_data| {
+        calls += 1;
+        assert_eq!(section_data.len(), text.len());
+        assert_eq!(result.section_offset, 0x123);
+        assert_eq!(&section_data[result.section_offset..result.section_offset + 4], &[0x48, 0x8B, 0x05, 0x90]);
+        assert_eq!(&elf[result.raw_offset..result.raw_offset + 3], &[0x48, 0x8B, 0x05]);
+        true
+    });
+
+    assert_eq!(result, Ok(true));
+    assert_eq!(calls, 1);
+}
@@ -0,0 +1,70 @@
+#[test]
+/// Tests that a self-overlapping pattern is reported at every position by default.
+fn scan_overlapping() {
+    let data = b"\x90\xAA\xAA\xAA\xAA\xAA\x90";
+
+    let offsets = aobscan::PatternBuilder::from_ida_style("AA AA")
+        .unwrap()
+        .build()
+        .scan_all(data);
+
+    assert_eq!(offsets, vec![1, 2, 3, 4]);
+}
+
+#[test]
+/// Tests that non-overlapping scans resume after the end of each match.
+fn scan_non_overlapping() {
+    let data = b"\x90\xAA\xAA\xAA\xAA\xAA\x90";
+
+    let offsets = aobscan::PatternBuilder::from_ida_style("AA AA")
+        .unwrap()
+        .with_overlapping(false)
+        .build()
+        .scan_all(data);
+
+    assert_eq!(offsets, vec![1, 3]);
+
+    // Leading wildcards are part of the span of the match.
+    let offsets = aobscan::PatternBuilder::from_ida_style("? AA")
+        .unwrap()
+        .with_overlapping(false)
+        .build()
+        .scan_all(data);
+
+    assert_eq!(offsets, vec![0, 2, 4]);
+}
+
+#[test]
+/// Tests that non-overlapping multi-threaded scans report the same matches as
+/// single-threaded scans, even when the matches straddle the chunk boundaries.
+fn scan_non_overlapping_multi_threaded() {
+    let mut data = vec![0x90u8; 0x1000];
+    // Runs of 0xAA bytes across the chunk boundaries of all the thread counts,
+    // starting at different positions relative to the boundaries.
+    for threads in 2..=8 {
+        for chunk in 1..threads {
+            let boundary = data.len() / threads * chunk;
+            let start = boundary - chunk % 3 - 1;
+            data[start..boundary + 7].fill(0xAA);
+        }
+    }
+
+    let build = |threads| {
+        aobscan::PatternBuilder::from_ida_style("AA AA AA")
+            .unwrap()
+            .with_overlapping(false)
+            .with_threads(threads)
+            .unwrap()
+            .build()
+    };
+
+    let expected = build(1).scan_all(&data);
+    assert!(!expected.is_empty());
+
+    // The thread count can't exceed the number of logical cores.
+    for threads in 2..=num_cpus::get().min(8) {
+        for _ in 0..16 {
+            assert_eq!(build(threads).scan_all(&data), expected);
+        }
+    }
+}
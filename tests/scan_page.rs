@@ -0,0 +1,44 @@
+/// Builds a buffer with 25 needles, one every 0x40 bytes.
+fn needles() -> (Vec<u8>, Vec<usize>) {
+    let mut data = vec![0u8; 0x1000];
+    let offsets = (0x10..0x10 + 25 * 0x40).step_by(0x40).collect::<Vec<_>>();
+    for &offset in &offsets {
+        data[offset..offset + 3].copy_from_slice(&[0x48, 0x8B, 0x05]);
+    }
+    (data, offsets)
+}
+
+#[test]
+/// Tests that the pages of a single-threaded scan cover all the matches in order.
+fn scan_page_single_threaded() {
+    let (data, offsets) = needles();
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    assert_eq!(pattern.scan_page(&data, 0, 10), (offsets[0..10].to_vec(), true));
+    assert_eq!(pattern.scan_page(&data, 1, 10), (offsets[10..20].to_vec(), true));
+    assert_eq!(pattern.scan_page(&data, 2, 10), (offsets[20..25].to_vec(), false));
+    assert_eq!(pattern.scan_page(&data, 3, 10), (vec![], false));
+
+    // A page that ends exactly at the last match has no next page.
+    assert_eq!(pattern.scan_page(&data, 4, 5), (offsets[20..25].to_vec(), false));
+}
+
+#[test]
+/// Tests that the pages of a multi-threaded scan are the same as the single-threaded ones.
+fn scan_page_multi_threaded() {
+    let (data, offsets) = needles();
+    let pattern = aobscan::Pattern::new(vec![0x48, 0x8B, 0x05], vec![true; 3], 4);
+
+    let mut paged = vec![];
+    for page in 0.. {
+        let (offsets, has_more) = pattern.scan_page(&data, page, 7);
+        paged.extend(offsets);
+        if !has_more {
+            break;
+        }
+    }
+
+    assert_eq!(paged, offsets);
+}
@@ -0,0 +1,28 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+/// Tests that a panic in the callback of a multi-threaded scan is propagated
+/// to the caller, and that the other threads stop instead of hanging on the
+/// poisoned callback mutex.
+fn scan_callback_panic_terminates() {
+    let mut data = vec![0u8; 0x10000];
+    for offset in (0..data.len()).step_by(0x10) {
+        data[offset..offset + 2].copy_from_slice(b"\x48\x8B");
+    }
+
+    let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], 4);
+    let calls = AtomicUsize::new(0);
+
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        pattern.scan(&data, |_| {
+            if calls.fetch_add(1, Ordering::Relaxed) == 0 {
+                panic!("callback failure");
+            }
+            true
+        })
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+}
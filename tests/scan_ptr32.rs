@@ -0,0 +1,71 @@
+use aobscan::Endian;
+
+/// Builds a buffer with the given pointers at the given offsets.
+fn planted_data(pointers: &[(usize, [u8; 4])]) -> Vec<u8> {
+    let mut data = vec![0u8; 0x40];
+    for &(offset, bytes) in pointers {
+        data[offset..offset + 4].copy_from_slice(&bytes);
+    }
+    data
+}
+
+#[test]
+/// Tests that the pointers to an address are found in the given byte order.
+fn scan_ptr32_exact() {
+    let data = planted_data(&[
+        (0x04, 0x00401000u32.to_le_bytes()),
+        (0x11, 0x00401000u32.to_le_bytes()),
+        (0x20, 0x00401000u32.to_be_bytes()),
+    ]);
+
+    let scan = |endian| {
+        let mut offsets = vec![];
+        aobscan::scan_ptr32(&data, 0x00401000, endian, |offset| {
+            offsets.push(offset);
+            true
+        });
+        offsets
+    };
+
+    assert_eq!(scan(Endian::Little), vec![0x04, 0x11]);
+    assert_eq!(scan(Endian::Big), vec![0x20]);
+
+    assert_eq!(
+        aobscan::PatternBuilder::from_u32(0x00401000, Endian::Little).build(),
+        aobscan::PatternBuilder::from_ida_style("00 10 40 00").unwrap().build()
+    );
+}
+
+#[test]
+/// Tests that the pointers into an address range are found at the aligned offsets.
+fn scan_ptr_range32() {
+    let data = planted_data(&[
+        (0x04, 0x00401000u32.to_le_bytes()),
+        (0x0A, 0x00402FFFu32.to_le_bytes()),
+        (0x10, 0x00403000u32.to_le_bytes()),
+        (0x18, 0x00400FFFu32.to_le_bytes()),
+        (0x20, 0x00402000u32.to_be_bytes()),
+    ]);
+
+    let scan = |endian, align| {
+        let mut pointers = vec![];
+        aobscan::scan_ptr_range32(&data, 0x00401000..0x00403000, endian, align, |offset, value| {
+            pointers.push((offset, value));
+            true
+        });
+        pointers
+    };
+
+    assert_eq!(scan(Endian::Little, 1), vec![(0x04, 0x00401000), (0x0A, 0x00402FFF)]);
+    assert_eq!(scan(Endian::Little, 0), scan(Endian::Little, 1));
+    assert_eq!(scan(Endian::Little, 4), vec![(0x04, 0x00401000)]);
+    assert_eq!(scan(Endian::Big, 4), vec![(0x20, 0x00402000)]);
+
+    // The callback can stop the scan.
+    let mut calls = 0;
+    assert!(aobscan::scan_ptr_range32(&data, 0x00401000..0x00403000, Endian::Little, 1, |_, _| {
+        calls += 1;
+        false
+    }));
+    assert_eq!(calls, 1);
+}
@@ -0,0 +1,36 @@
+#[test]
+/// Tests that the fields of the report are consistent with the scan.
+fn scan_report_fields() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x1000];
+    for offset in [0x800, 0x10, 0xFFC] {
+        data[offset..offset + 4].copy_from_slice(&[0x48, 0x8B, 0x00, 0x05]);
+    }
+
+    let report = pattern.scan_report(&data);
+    assert_eq!(report.pattern, "48 8B ? 05");
+    assert_eq!(report.data_len, data.len());
+    assert_eq!(report.threads, 1);
+    assert_eq!(report.matches, vec![0x10, 0x800, 0xFFC]);
+    assert_eq!(report.matches.len(), pattern.scan_all(&data).len());
+    assert_eq!(report.density, pattern.density(&data));
+
+    let summary = report.to_string();
+    assert!(summary.contains("pattern: 48 8B ? 05"));
+    assert!(summary.contains("matches: 3"));
+}
+
+#[test]
+/// Tests the report of data shorter than the pattern.
+fn scan_report_short_data() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 05")
+        .unwrap()
+        .build();
+
+    let report = pattern.scan_report(&[0x48, 0x8B]);
+    assert!(report.matches.is_empty());
+    assert_eq!(report.density, 0.0);
+}
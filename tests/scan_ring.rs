@@ -0,0 +1,35 @@
+/// Scans the circular buffer, and returns the logical offsets of the matches.
+fn ring(pattern: &aobscan::Pattern, data: &[u8], head: usize) -> Vec<usize> {
+    let mut offsets = vec![];
+    pattern.scan_ring(data, head, |offset| {
+        offsets.push(offset);
+        true
+    });
+    offsets.sort_unstable();
+    offsets
+}
+
+#[test]
+/// Tests that a needle straddling the wrap point of the buffer is found.
+fn scan_ring_wrap() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05 90")
+        .unwrap()
+        .build();
+
+    // The needle starts 2 bytes before the end of the buffer, and continues at its start.
+    let mut data = vec![0u8; 0x40];
+    data[0x3E..0x40].copy_from_slice(&[0x48, 0x8B]);
+    data[0x00..0x02].copy_from_slice(&[0x05, 0x90]);
+    // Another needle that does not span the wrap point.
+    data[0x10..0x14].copy_from_slice(&[0x48, 0x8B, 0x05, 0x90]);
+
+    // The head is where the logical buffer starts.
+    assert_eq!(ring(&pattern, &data, 0x20), vec![0x1E, 0x30]);
+    assert_eq!(ring(&pattern, &data, 0x02), vec![0x0E, 0x3C]);
+    // Heads past the end of the data wrap around.
+    assert_eq!(ring(&pattern, &data, 0x60), vec![0x1E, 0x30]);
+
+    // Without rotation, the straddling needle is not found.
+    assert_eq!(ring(&pattern, &data, 0x00), vec![0x10]);
+    assert_eq!(ring(&pattern, &[], 0x10), Vec::<usize>::new());
+}
@@ -0,0 +1,41 @@
+#[test]
+/// Tests that clustered and isolated matches are encoded as runs, and decoded back.
+fn scan_rle_runs() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("CC")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0x90u8; 0x100];
+    data[0x10..0x18].fill(0xCC);
+    data[0x40] = 0xCC;
+    data[0x42] = 0xCC;
+    data[0xFF] = 0xCC;
+
+    let runs = pattern.scan_rle(&data);
+    assert_eq!(runs, vec![(0x10, 8), (0x40, 1), (0x42, 1), (0xFF, 1)]);
+    assert_eq!(aobscan::rle_to_offsets(&runs), pattern.scan_all(&data));
+}
+
+#[test]
+/// Tests that the runs of multi-threaded scans are the same as the single-threaded ones.
+fn scan_rle_multi_threaded() {
+    let mut data = vec![0x90u8; 0x1000];
+    data[0x7F0..0x810].fill(0xCC);
+    data[0xC00] = 0xCC;
+
+    for threads in [1, 2, 3, 8] {
+        let pattern = aobscan::Pattern::new(vec![0xCC, 0xCC], vec![true, true], threads);
+        assert_eq!(pattern.scan_rle(&data), vec![(0x7F0, 0x1F)]);
+    }
+}
+
+#[test]
+/// Tests that the scan without matches has no runs.
+fn scan_rle_empty() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("CC")
+        .unwrap()
+        .build();
+
+    assert!(pattern.scan_rle(&[0x90; 0x20]).is_empty());
+    assert!(aobscan::rle_to_offsets(&[]).is_empty());
+}
@@ -0,0 +1,53 @@
+#![cfg(feature = "object-scan")]
+
+use object::{BinaryFormat, SectionKind};
+
+use aobscan::ObjectScan;
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that only the sections of the specified kind are scanned.
+fn scan_sections_of_kind() {
+    let needle = b"\x55\x48\x89\xE5";
+    let elf = build_object(
+        BinaryFormat::Elf,
+        &[
+            (".text", SectionKind::Text, &[b"\x90\x90".as_slice(), needle].concat()),
+            (".data", SectionKind::Data, needle),
+            (".text.hot", SectionKind::Text, &[needle.as_slice(), b"\xC3"].concat()),
+            (".rodata", SectionKind::ReadOnlyData, needle),
+        ],
+    );
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("55 48 89 E5")
+        .unwrap()
+        .build();
+
+    let mut results = vec![];
+    let result = pattern.scan_sections_of_kind(&elf, SectionKind::Text, |result| {
+        results.push((result.section_name, result.section_offset));
+        true
+    });
+
+    // The needles in the data sections are excluded.
+    assert_eq!(result, Ok(true));
+    assert_eq!(
+        results,
+        vec![(".text".to_string(), 2), (".text.hot".to_string(), 0)]
+    );
+
+    // Stopping the scan in the first section skips the others.
+    let mut count = 0;
+    pattern.scan_sections_of_kind(&elf, SectionKind::Text, |_| {
+        count += 1;
+        false
+    }).unwrap();
+    assert_eq!(count, 1);
+
+    assert_eq!(
+        pattern.scan_sections_of_kind(&elf, SectionKind::Tls, |_| true),
+        Err(aobscan::ObjectError::SectionNotFound)
+    );
+}
@@ -0,0 +1,42 @@
+/// Scans the table, and returns the indices of the matching entries.
+fn strided(pattern: &aobscan::Pattern, data: &[u8], base: usize, stride: usize, count: usize) -> Vec<usize> {
+    let mut indices = vec![];
+    pattern.scan_strided(data, base, stride, count, |index| {
+        indices.push(index);
+        true
+    });
+    indices
+}
+
+#[test]
+/// Tests that the matching entries of a table are reported by index.
+fn scan_strided_table() {
+    // A table of 8 entries of 8 bytes, starting at 0x10.
+    let mut data = vec![0u8; 0x60];
+    for index in [0, 3, 4, 7] {
+        let entry = 0x10 + index * 8;
+        data[entry..entry + 4].copy_from_slice(&[0x40, 0x10, 0x00, 0x00]);
+    }
+    // A match that is not aligned to the entries is ignored.
+    data[0x2C..0x30].copy_from_slice(&[0x40, 0x10, 0x00, 0x00]);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("40 10 00 00")
+        .unwrap()
+        .build();
+
+    assert_eq!(strided(&pattern, &data, 0x10, 8, 8), vec![0, 3, 4, 7]);
+    assert_eq!(strided(&pattern, &data, 0x10, 8, 4), vec![0, 3]);
+    // Entries past the end of the data never match.
+    assert_eq!(strided(&pattern, &data, 0x10, 8, 0x100), vec![0, 3, 4, 7]);
+    assert_eq!(strided(&pattern, &data, 0x10, usize::MAX, 4), vec![0]);
+
+    // The callback can stop the scan.
+    let mut calls = 0;
+    assert!(pattern.scan_strided(&data, 0x10, 8, 8, |_| {
+        calls += 1;
+        false
+    }));
+    assert_eq!(calls, 1);
+
+    assert!(!pattern.scan_strided(&data, 0x18, 8, 2, |_| true));
+}
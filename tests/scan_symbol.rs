@@ -0,0 +1,76 @@
+#![cfg(feature = "object-scan")]
+
+use object::{Architecture, BinaryFormat, Endianness, SymbolFlags, SymbolKind, SymbolScope};
+use object::write::{Object, StandardSection, Symbol, SymbolSection};
+
+use aobscan::{ObjectError, ObjectScan};
+
+/// Builds an x86-64 object file with a text section containing the given functions.<br>
+/// The functions are laid out contiguously in the section, in the given order.
+fn build_object_with_functions(format: BinaryFormat, functions: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut object = Object::new(format, Architecture::X86_64, Endianness::Little);
+    let section = object.section_id(StandardSection::Text);
+
+    for (name, code) in functions {
+        let offset = object.append_section_data(section, code, 1);
+        object.add_symbol(Symbol {
+            name: name.as_bytes().to_vec(),
+            value: offset,
+            size: code.len() as u64,
+            kind: SymbolKind::Text,
+            scope: SymbolScope::Linkage,
+            weak: false,
+            section: SymbolSection::Section(section),
+            flags: SymbolFlags::None,
+        });
+    }
+
+    object.write().unwrap()
+}
+
+#[test]
+/// Tests that only the bytes of the symbol are scanned, and that the offsets are
+/// relative to the start of the symbol.
+fn scan_symbol_bounds() {
+    let needle = b"\x55\x48\x89\xE5";
+    let pattern = aobscan::PatternBuilder::from_ida_style("55 48 89 E5")
+        .unwrap()
+        .build();
+
+    for format in [BinaryFormat::Elf, BinaryFormat::Coff, BinaryFormat::MachO] {
+        let object = build_object_with_functions(format, &[
+            ("before", &[needle.as_slice(), b"\xC3"].concat()),
+            ("target", &[b"\x90\x90\x90".as_slice(), needle, needle, b"\xC3"].concat()),
+            ("after", &[needle.as_slice(), b"\xC3"].concat()),
+        ]);
+
+        // Mach-O symbols are mangled with a leading underscore.
+        let target = match format {
+            BinaryFormat::MachO => "_target",
+            _ => "target",
+        };
+
+        let mut offsets = vec![];
+        let result = pattern.scan_symbol(&object, target, |offset| {
+            offsets.push(offset);
+            true
+        });
+
+        assert_eq!(result, Ok(true), "{:?}", format);
+        offsets.sort_unstable();
+        assert_eq!(offsets, vec![3, 7], "{:?}", format);
+    }
+}
+
+#[test]
+/// Tests that the scan of a missing symbol returns an error.
+fn scan_symbol_not_found() {
+    let object = build_object_with_functions(BinaryFormat::Elf, &[("target", b"\x90\xC3")]);
+    let pattern = aobscan::PatternBuilder::from_ida_style("90 C3")
+        .unwrap()
+        .build();
+
+    assert_eq!(pattern.scan_symbol(&object, "target", |_| true), Ok(true));
+    assert_eq!(pattern.scan_symbol(&object, "Target", |_| true), Err(ObjectError::SymbolNotFound));
+    assert_eq!(pattern.scan_symbol(b"not an object", "target", |_| true), Err(ObjectError::InvalidObject));
+}
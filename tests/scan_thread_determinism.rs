@@ -0,0 +1,50 @@
+use rand::Rng;
+
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that the same matches are found regardless of the number of threads,
+/// including the matches that straddle the boundaries between the chunks.
+fn scan_thread_determinism() {
+    let signature = b"\x55\x48\x89\x00\x48\x8B";
+    let mask = [true, true, true, false, true, true];
+    let mut rng = rand::thread_rng();
+
+    let mut thread_counts = vec![1, 2, 3, 4, 7, num_cpus()];
+    thread_counts.dedup();
+
+    for _ in 0..32 {
+        let len = rng.gen_range(1..0x4000);
+        let mut data = random_bytes(len);
+
+        // Plant the needle around the chunk boundaries of all the thread counts.
+        for &threads in &thread_counts {
+            for chunk in 1..threads {
+                let boundary = len / threads * chunk;
+                let offset = boundary.saturating_sub(rng.gen_range(0..signature.len()));
+                if offset + signature.len() <= len {
+                    data[offset..offset + signature.len()].copy_from_slice(signature);
+                }
+            }
+        }
+
+        // Plant the needle at random offsets, at the start and at the end of the data.
+        if len >= signature.len() {
+            for offset in [0, len - signature.len(), rng.gen_range(0..=len - signature.len())] {
+                data[offset..offset + signature.len()].copy_from_slice(signature);
+            }
+        }
+
+        let expected = naive_scan(&data, signature, &mask);
+        for &threads in &thread_counts {
+            let pattern = aobscan::Pattern::new(signature.to_vec(), mask.to_vec(), threads);
+            assert_eq!(pattern.scan_all(&data), expected, "threads: {}, length: {}", threads, len);
+        }
+    }
+}
+
+fn num_cpus() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
@@ -0,0 +1,76 @@
+use std::sync::Mutex;
+
+#[test]
+/// Tests that the thread indices are in the range of the thread count, and that
+/// they match the chunks of the data.
+fn scan_with_thread_id_range() {
+    let mut data = vec![0u8; 0x1000];
+    for offset in (0..data.len()).step_by(0x40) {
+        data[offset..offset + 2].copy_from_slice(&[0x48, 0x8B]);
+    }
+
+    for threads in [1, 2, 3, 8] {
+        let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], threads);
+
+        let hits = Mutex::new(vec![]);
+        pattern.scan_with_thread_id(&data, |offset, thread| {
+            hits.lock().unwrap().push((offset, thread));
+            true
+        });
+
+        let mut hits = hits.into_inner().unwrap();
+        hits.sort_unstable();
+        assert_eq!(hits.len(), 0x40);
+        assert!(hits.iter().all(|&(_, thread)| thread < threads));
+
+        // The threads own consecutive chunks, so the indices never decrease.
+        assert!(hits.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert_eq!(hits.first().unwrap().1, 0);
+        assert_eq!(hits.last().unwrap().1, threads - 1);
+    }
+}
+
+#[test]
+/// Tests that the thread indices are those of the threads that run the callback,
+/// including the non-overlapping scans, whose callbacks run on the calling thread.
+fn scan_with_thread_id_worker() {
+    let mut data = vec![0u8; 0x1000];
+    for offset in (0..data.len()).step_by(0x40) {
+        data[offset..offset + 2].copy_from_slice(&[0x48, 0x8B]);
+    }
+
+    let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], 4);
+    let hits = Mutex::new(vec![]);
+    pattern.scan_with_thread_id(&data, |_, thread| {
+        hits.lock().unwrap().push((thread, std::thread::current().id()));
+        true
+    });
+
+    // Each index always belongs to the same worker, and each worker has a single index.
+    let hits = hits.into_inner().unwrap();
+    assert_eq!(hits.len(), 0x40);
+    for &(thread, id) in &hits {
+        assert!(hits.iter().all(|&(other, other_id)| (other == thread) == (other_id == id)));
+    }
+
+    // The thread count can't exceed the number of logical cores.
+    for threads in 1..=num_cpus::get().min(4) {
+        let pattern = aobscan::PatternBuilder::from_ida_style("48 8B")
+            .unwrap()
+            .with_overlapping(false)
+            .with_threads(threads)
+            .unwrap()
+            .build();
+
+        let caller = std::thread::current().id();
+        let hits = Mutex::new(vec![]);
+        pattern.scan_with_thread_id(&data, |_, thread| {
+            hits.lock().unwrap().push((thread, std::thread::current().id()));
+            true
+        });
+
+        let hits = hits.into_inner().unwrap();
+        assert_eq!(hits.len(), 0x40);
+        assert!(hits.iter().all(|&hit| hit == (0, caller)));
+    }
+}
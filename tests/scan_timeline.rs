@@ -0,0 +1,47 @@
+/// Builds the labeled patterns used by the tests.
+fn labeled_patterns() -> Vec<(String, aobscan::Pattern)> {
+    vec![
+        ("call".to_string(), aobscan::PatternBuilder::from_ida_style("E8 ? ? ? ?").unwrap().build()),
+        ("prologue".to_string(), aobscan::PatternBuilder::from_ida_style("55 48 89 E5").unwrap().build()),
+    ]
+}
+
+#[test]
+/// Tests that the matches of all the patterns are merged in order, with their labels.
+fn scan_timeline_sorted_and_labeled() {
+    let mut data = vec![0u8; 0x100];
+    data[0x10..0x14].copy_from_slice(&[0x55, 0x48, 0x89, 0xE5]);
+    data[0x20] = 0xE8;
+    data[0x80..0x84].copy_from_slice(&[0x55, 0x48, 0x89, 0xE5]);
+    data[0x40] = 0xE8;
+    data[0xF0] = 0xE8;
+
+    let timeline = aobscan::scan_timeline(&labeled_patterns(), &data);
+
+    let expected = vec![
+        (0x10, "prologue"),
+        (0x20, "call"),
+        (0x40, "call"),
+        (0x80, "prologue"),
+        (0xF0, "call"),
+    ];
+    assert_eq!(
+        timeline.iter().map(|(offset, label)| (*offset, label.as_str())).collect::<Vec<_>>(),
+        expected
+    );
+}
+
+#[test]
+/// Tests that the matches at the same offset keep the order of the patterns.
+fn scan_timeline_same_offset() {
+    let patterns = vec![
+        ("second".to_string(), aobscan::PatternBuilder::from_ida_style("48 8B").unwrap().build()),
+        ("first".to_string(), aobscan::PatternBuilder::from_ida_style("48").unwrap().build()),
+    ];
+
+    assert_eq!(
+        aobscan::scan_timeline(&patterns, &[0x48, 0x8B]),
+        vec![(0, "second".to_string()), (0, "first".to_string())]
+    );
+    assert!(aobscan::scan_timeline(&[], &[0x48, 0x8B]).is_empty());
+}
@@ -0,0 +1,36 @@
+use aobscan::OutputFormat;
+
+/// Scans a buffer with two matches and returns the written output.
+fn scan_to_string(format: OutputFormat) -> String {
+    let mut data = vec![0u8; 0x2000];
+    data[0x10..0x12].copy_from_slice(b"\x48\x8B");
+    data[0x1000..0x1002].copy_from_slice(b"\x48\x8B");
+
+    let mut out = vec![];
+    let written = aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build()
+        .scan_to_writer(&data, &mut out, format)
+        .unwrap();
+
+    assert_eq!(written, 2);
+    String::from_utf8(out).unwrap()
+}
+
+#[test]
+fn scan_to_writer_plain() {
+    assert_eq!(scan_to_string(OutputFormat::PlainOffsets), "16\n4096\n");
+}
+
+#[test]
+fn scan_to_writer_hex() {
+    assert_eq!(scan_to_string(OutputFormat::Hex), "0x10\n0x1000\n");
+}
+
+#[test]
+fn scan_to_writer_json() {
+    assert_eq!(
+        scan_to_string(OutputFormat::Json),
+        "{\"offset\":16}\n{\"offset\":4096}\n"
+    );
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "tracing")]
+
+use object::{BinaryFormat, SectionKind};
+use tracing_test::traced_test;
+
+use aobscan::ObjectScan;
+use common::*;
+
+mod common;
+
+#[test]
+#[traced_test]
+/// Tests that the scans are recorded in a span, with an event for each match.
+fn scan_tracing_span() {
+    let mut data = vec![0u8; 0x100];
+    data[0x42..0x44].copy_from_slice(&[0x48, 0x8B]);
+
+    aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build()
+        .scan(&data, |_| true);
+
+    assert!(logs_contain("scan{pattern=48 8B data_len=256 threads=1}"));
+    assert!(logs_contain("match offset=66"));
+}
+
+#[test]
+#[traced_test]
+/// Tests that the object scans are recorded in a span that contains the section scans.
+fn scan_object_tracing_span() {
+    let mut text = vec![0xCCu8; 0x100];
+    text[0x10..0x12].copy_from_slice(&[0x48, 0x8B]);
+    let elf = build_object(BinaryFormat::Elf, &[(".text", SectionKind::Text, &text)]);
+
+    aobscan::PatternBuilder::from_ida_style("48 8B")
+        .unwrap()
+        .build()
+        .scan_object(&elf, ".text", |_| true)
+        .unwrap();
+
+    assert!(logs_contain("scan_object{pattern=48 8B"));
+    assert!(logs_contain("section=\".text\""));
+    assert!(logs_contain("match offset=16"));
+}
@@ -0,0 +1,49 @@
+/// XORs every byte of the region with the key.
+fn xor(region: &mut [u8]) {
+    region.iter_mut().for_each(|byte| *byte ^= 0x5A);
+}
+
+#[test]
+/// Tests that the regions starting at each match are transformed in place.
+fn scan_transform_xor() {
+    let mut data = vec![0u8; 0x40];
+    data[0x08..0x0C].copy_from_slice(b"\xB1\x0B\x12\x3F");
+    data[0x20..0x24].copy_from_slice(b"\xB1\x0B\x36\x35");
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("B1 0B")
+        .unwrap()
+        .build();
+    assert!(pattern.scan_transform(&mut data, 4, xor));
+
+    let mut expected = vec![0u8; 0x40];
+    expected[0x08..0x0C].copy_from_slice(b"\xEB\x51He");
+    expected[0x20..0x24].copy_from_slice(b"\xEB\x51lo");
+    assert_eq!(data, expected);
+
+    // The transformed data no longer contains the pattern.
+    assert!(!pattern.scan_transform(&mut data, 4, xor));
+    assert_eq!(data, expected);
+}
+
+#[test]
+/// Tests that overlapping regions are transformed in order, and that the
+/// regions are clamped to the end of the data.
+fn scan_transform_overlapping() {
+    let mut data = vec![0xAAu8, 0xAA, 0x00, 0x00];
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("AA")
+        .unwrap()
+        .build();
+
+    let mut calls = vec![];
+    let calls_cell = std::cell::RefCell::new(&mut calls);
+    pattern.scan_transform(&mut data, 8, |region| {
+        calls_cell.borrow_mut().push(region.len());
+        region.iter_mut().for_each(|byte| *byte = byte.wrapping_add(1));
+    });
+
+    // The matches are found in the original data, so the second one is transformed
+    // even though the first transform changed its bytes.
+    assert_eq!(calls, vec![4, 3]);
+    assert_eq!(data, vec![0xAB, 0xAC, 0x02, 0x02]);
+}
@@ -0,0 +1,49 @@
+use rand::{Rng, RngCore, SeedableRng};
+
+/// Finds the matches of the pattern by comparing every position of the data.
+fn brute_force(signature: &[u8], mask: &[bool], data: &[u8]) -> Vec<usize> {
+    (0..=data.len().saturating_sub(signature.len()))
+        .filter(|&offset| offset + signature.len() <= data.len())
+        .filter(|&offset| {
+            signature.iter()
+                .zip(mask)
+                .enumerate()
+                .all(|(i, (&byte, &fixed))| !fixed || data[offset + i] == byte)
+        })
+        .collect()
+}
+
+#[test]
+/// Tests that the verified matches are the same as a brute-force scan on random data.
+fn scan_verified_random() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x943);
+    let signature = vec![0x00, 0x48, 0x00, 0x8B];
+    let mask = vec![false, true, false, true];
+
+    for threads in [1, 2, 3, 8] {
+        for _ in 0..8 {
+            let mut data = vec![0u8; rng.gen_range(0..0x4000)];
+            rng.fill_bytes(&mut data);
+            // Restrict the values, so that there are plenty of matches.
+            data.iter_mut().for_each(|byte| *byte = [0x48, 0x8B, 0x90][*byte as usize % 3]);
+
+            let pattern = aobscan::Pattern::new(signature.clone(), mask.clone(), threads);
+            assert_eq!(pattern.scan_verified(&data), brute_force(&signature, &mask, &data));
+        }
+    }
+}
+
+#[test]
+/// Tests the single-position check at the edges of the data.
+fn matches_at_bounds() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? 48 8B")
+        .unwrap()
+        .build();
+
+    let data = [0x90, 0x48, 0x8B, 0x48];
+    assert!(pattern.matches_at(&data, 0));
+    assert!(!pattern.matches_at(&data, 1));
+    // The match would extend past the end of the data.
+    assert!(!pattern.matches_at(&data, 2));
+    assert!(!pattern.matches_at(&data, usize::MAX));
+}
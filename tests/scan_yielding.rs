@@ -0,0 +1,62 @@
+/// Builds a buffer with a match of `48 8B 05` every 0x100 bytes, starting
+/// 2 bytes before each multiple of 0x100, so that the matches straddle the windows.
+fn planted_data(len: usize) -> Vec<u8> {
+    let mut data = vec![0u8; len];
+    for offset in (0x100..len).step_by(0x100) {
+        data[offset - 2..offset + 1].copy_from_slice(&[0x48, 0x8B, 0x05]);
+    }
+    data
+}
+
+/// Scans the data, and returns the matches and the number of yields.
+fn scan(pattern: &aobscan::Pattern, data: &[u8], yield_every: usize) -> (Vec<usize>, usize) {
+    let mut yields = 0;
+    let mut offsets = vec![];
+    pattern.scan_yielding(data, yield_every, || yields += 1, |offset| {
+        offsets.push(offset);
+        true
+    });
+    (offsets, yields)
+}
+
+#[test]
+/// Tests that the yield function is called once for every complete window.
+fn scan_yielding_count() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05")
+        .unwrap()
+        .build();
+
+    let data = planted_data(0x1000);
+    let expected = pattern.scan_all(&data);
+    assert_eq!(expected.len(), 0xF);
+
+    assert_eq!(scan(&pattern, &data, 0x100), (expected.clone(), 0x10));
+    assert_eq!(scan(&pattern, &data, 0x300), (expected.clone(), 5));
+    assert_eq!(scan(&pattern, &data, 1), (expected.clone(), 0x1000));
+    assert_eq!(scan(&pattern, &data, 0), (expected.clone(), 0));
+    assert_eq!(scan(&pattern, &data, 0x2000), (expected, 0));
+}
+
+#[test]
+/// Tests that the matching options are applied across the windows.
+fn scan_yielding_options() {
+    let data = [0xAAu8; 0x40];
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("AA AA AA")
+        .unwrap()
+        .with_overlapping(false)
+        .build();
+    assert_eq!(scan(&pattern, &data, 4).0, pattern.scan_all(&data));
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("? AA")
+        .unwrap()
+        .with_min_distance(5)
+        .build();
+    assert_eq!(scan(&pattern, &data, 3).0, pattern.scan_all(&data));
+
+    // Stopping the scan also stops the yields.
+    // (the scan stops at the match at 20, in the sixth window)
+    let mut yields = 0;
+    pattern.scan_yielding(&data, 4, || yields += 1, |offset| offset < 0x10);
+    assert_eq!(yields, 5);
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "object-scan")]
+
+use object::{BinaryFormat, SectionKind};
+
+use aobscan::{ObjectError, ObjectScan, SectionScanner};
+use common::*;
+
+mod common;
+
+const NEEDLE: &[u8] = &[0x48, 0x8B, 0x05, 0x90];
+
+#[test]
+/// Tests that multiple sections can be scanned with a single parse of the file.
+fn section_scanner_sections() {
+    let macho = build_object(
+        BinaryFormat::MachO,
+        &[
+            ("__text", SectionKind::Text, &[b"\x90\x90".as_slice(), NEEDLE].concat()),
+            ("__stubs", SectionKind::Text, NEEDLE),
+        ],
+    );
+
+    let pattern = aobscan::PatternBuilder::from_hex_string("488b0590")
+        .unwrap()
+        .build();
+    let scanner = SectionScanner::new(&macho).unwrap();
+
+    for (section, offset) in [("__text", 2), ("__stubs", 0)] {
+        let mut results = vec![];
+        let found = scanner.scan(&pattern, section, |result| {
+            results.push(result);
+            true
+        });
+
+        assert_eq!(found, Ok(true));
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].section_name, section);
+        assert_eq!(results[0].section_offset, offset);
+
+        // The results are the same as the ones of a scan that parses the file.
+        let mut expected = vec![];
+        pattern.scan_object(&macho, section, |result| {
+            expected.push(result);
+            true
+        }).unwrap();
+        assert_eq!(results, expected);
+    }
+
+    assert_eq!(scanner.scan(&pattern, "__const", |_| true), Err(ObjectError::SectionNotFound));
+}
+
+#[test]
+/// Tests that the scanner can't be created from invalid data.
+fn section_scanner_invalid() {
+    assert!(matches!(SectionScanner::new(b"not an object"), Err(ObjectError::InvalidObject)));
+}
@@ -0,0 +1,72 @@
+use rand::{Rng, SeedableRng};
+
+/// Finds the matches of a signature by comparing its bits at every offset.<br>
+/// As in the patterns, the trailing wildcards are ignored.
+fn naive_scan(data: &[u8], signature: &[u8], mask: &[u8]) -> Vec<usize> {
+    let length = mask.iter().rposition(|&bits| bits != 0).map_or(0, |last| last + 1);
+    let (signature, mask) = (&signature[..length], &mask[..length]);
+
+    (0..=data.len().saturating_sub(signature.len()))
+        .filter(|&offset| {
+            signature.iter().zip(mask).enumerate()
+                .all(|(index, (&sig, &bits))| data[offset + index] & bits == sig & bits)
+        })
+        .collect()
+}
+
+/// Builds a pattern from the bit masks of the signature bytes.
+fn build(signature: &[u8], mask: &[u8]) -> aobscan::Pattern {
+    let bitmask = mask.iter().map(|bits| format!("{:08b}", bits)).collect::<String>();
+    aobscan::PatternBuilder::from_bitmask(signature, &bitmask)
+        .unwrap()
+        .build()
+}
+
+#[test]
+/// Tests that the short signatures report the same matches as a comparison of every
+/// byte, for every length up to 16 bytes and with wildcards in every lane.
+fn short_compare_lengths_and_lanes() {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(0x997);
+
+    for length in 2..=16 {
+        for wildcard in 0..length {
+            let signature = (0..length).map(|_| rng.gen_range(0x40..0x44)).collect::<Vec<u8>>();
+            let mut mask = vec![0xFFu8; length];
+            mask[wildcard] = 0x00;
+            if length > 2 {
+                // Also leave a partially fixed byte in another lane.
+                mask[(wildcard + 1) % length] = 0xF0;
+            }
+
+            // Make the matches frequent, and plant one that ends with the data.
+            let mut data = (0..0x400).map(|_| rng.gen_range(0x40..0x44)).collect::<Vec<u8>>();
+            let end = data.len() - length;
+            data[end..].copy_from_slice(&signature);
+
+            let pattern = build(&signature, &mask);
+            let expected = naive_scan(&data, &signature, &mask);
+            assert!(expected.contains(&end));
+            assert_eq!(pattern.scan_all(&data), expected, "{:02X?} {:02X?}", signature, mask);
+        }
+    }
+}
+
+#[test]
+/// Tests that the data shorter than 16 bytes is compared without reading past its end.
+fn short_compare_short_data() {
+    for length in 2..=16 {
+        let signature = (0..length as u8).collect::<Vec<u8>>();
+        let pattern = aobscan::Pattern::new(signature.clone(), vec![true; length], 1);
+
+        for padding in 0..3 {
+            let mut data = vec![0xCC; padding];
+            data.extend_from_slice(&signature);
+            assert_eq!(pattern.scan_all(&data), vec![padding]);
+            assert!(pattern.matches_at(&data, padding));
+
+            // The padding lanes are not compared with the zeros after the data.
+            data.pop();
+            assert!(pattern.scan_all(&data).is_empty());
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that a pattern made of a single fixed byte finds every occurrence of the byte.
+fn single_byte_all_occurrences() {
+    let data = random_bytes_seeded(0x1000, 0x0B5CA4);
+    let expected = (0..data.len())
+        .filter(|&offset| data[offset] == 0xCC)
+        .collect::<Vec<_>>();
+    assert!(!expected.is_empty());
+
+    for threads in [1, 2, 3, 8] {
+        let pattern = aobscan::Pattern::new(vec![0xCC], vec![true], threads);
+        assert_eq!(pattern.scan_all(&data), expected, "{} threads", threads);
+    }
+
+    // The leading and trailing wildcards are removed, so this is a single byte as well.
+    let pattern = aobscan::PatternBuilder::from_ida_style("? CC ?")
+        .unwrap()
+        .build();
+    let shifted = expected.iter()
+        .filter_map(|&offset| offset.checked_sub(1))
+        .collect::<Vec<_>>();
+    assert_eq!(pattern.scan_all(&data), shifted);
+}
+
+#[test]
+/// Tests that the matching options are applied to the single-byte patterns.
+fn single_byte_options() {
+    let data = [0xCC, 0xCC, 0x90, 0xCC, 0xCC, 0xCC];
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("CC")
+        .unwrap()
+        .with_min_distance(2)
+        .build();
+    assert_eq!(pattern.scan_all(&data), vec![0, 3, 5]);
+
+    let pattern = aobscan::PatternBuilder::from_ida_style("CC")
+        .unwrap()
+        .with_exclusion(1, 0x90)
+        .build();
+    assert_eq!(pattern.scan_all(&data), vec![0, 3, 4, 5]);
+
+    let mut count = 0;
+    let pattern = aobscan::PatternBuilder::from_ida_style("CC").unwrap().build();
+    pattern.scan(data, |_| {
+        count += 1;
+        count < 2
+    });
+    assert_eq!(count, 2);
+}
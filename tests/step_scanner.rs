@@ -0,0 +1,41 @@
+#[test]
+/// Tests that each step returns the next match, and moves the cursor past it.
+fn step_scanner_needles() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B ? 05")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x100];
+    data[0x20..0x24].copy_from_slice(&[0x48, 0x8B, 0x00, 0x05]);
+    data[0xC0..0xC4].copy_from_slice(&[0x48, 0x8B, 0x01, 0x05]);
+
+    let mut scanner = aobscan::StepScanner::new(&pattern, &data);
+    assert_eq!(scanner.position(), 0);
+
+    assert_eq!(scanner.step(), Some(0x20));
+    assert_eq!(scanner.position(), 0x21);
+
+    assert_eq!(scanner.step(), Some(0xC0));
+    assert_eq!(scanner.position(), 0xC1);
+
+    assert_eq!(scanner.step(), None);
+    assert_eq!(scanner.position(), data.len());
+    assert_eq!(scanner.step(), None);
+}
+
+#[test]
+/// Tests that the steps of non-overlapping patterns skip the overlapping matches.
+fn step_scanner_non_overlapping() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("CC CC")
+        .unwrap()
+        .with_overlapping(false)
+        .build();
+
+    let data = [0xCC; 5];
+    let mut scanner = aobscan::StepScanner::new(&pattern, &data);
+
+    assert_eq!(scanner.step(), Some(0));
+    assert_eq!(scanner.position(), 2);
+    assert_eq!(scanner.step(), Some(2));
+    assert_eq!(scanner.step(), None);
+}
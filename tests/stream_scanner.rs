@@ -0,0 +1,71 @@
+#[test]
+/// Tests that a match straddling two chunks is reported with its stream offset.
+fn stream_scanner_straddling_match() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05 ? 90")
+        .unwrap()
+        .build();
+
+    let mut first = vec![0u8; 0x100];
+    first[0xFE..].copy_from_slice(&[0x48, 0x8B]);
+    let mut second = vec![0u8; 0x100];
+    second[..3].copy_from_slice(&[0x05, 0xFF, 0x90]);
+    second[0x80..0x85].copy_from_slice(&[0x48, 0x8B, 0x05, 0x00, 0x90]);
+
+    let mut offsets = vec![];
+    let mut scanner = aobscan::StreamScanner::new(&pattern);
+    scanner.push(&first, |offset| offsets.push(offset));
+    assert!(offsets.is_empty());
+    scanner.push(&second, |offset| offsets.push(offset));
+    scanner.finish();
+
+    assert_eq!(offsets, vec![0xFE, 0x180]);
+}
+
+#[test]
+/// Tests that the matches are the same regardless of how the stream is split.
+fn stream_scanner_chunk_sizes() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? AA BB ? CC")
+        .unwrap()
+        .build();
+
+    let mut data = vec![0u8; 0x400];
+    for offset in [0x0, 0x41, 0x1FF, 0x3FB] {
+        data[offset..offset + 5].copy_from_slice(&[0x11, 0xAA, 0xBB, 0x22, 0xCC]);
+    }
+    let expected = pattern.scan_all(&data).into_iter().map(|offset| offset as u64).collect::<Vec<_>>();
+    assert_eq!(expected.len(), 4);
+
+    let mut scanner = aobscan::StreamScanner::new(&pattern);
+    for chunk_size in [1, 2, 3, 7, 0x40, 0x400] {
+        let mut offsets = vec![];
+        for chunk in data.chunks(chunk_size) {
+            scanner.push(chunk, |offset| offsets.push(offset));
+        }
+        scanner.finish();
+
+        assert_eq!(offsets, expected);
+    }
+}
+
+#[test]
+/// Tests that the matches that omit optional bytes at the end of a chunk are
+/// not reported again with the next chunk.
+fn stream_scanner_optional_bytes() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("55 (48) 89")
+        .unwrap()
+        .build();
+
+    let mut offsets = vec![];
+    let mut scanner = aobscan::StreamScanner::new(&pattern);
+    scanner.push(b"\x90\x55\x89", |offset| offsets.push(offset));
+    scanner.push(b"\x90\x90\x90", |offset| offsets.push(offset));
+    scanner.push(b"\x55\x48", |offset| offsets.push(offset));
+    scanner.push(b"\x89\x55", |offset| offsets.push(offset));
+    scanner.push(b"\x89", |offset| offsets.push(offset));
+    scanner.finish();
+
+    let data = b"\x90\x55\x89\x90\x90\x90\x55\x48\x89\x55\x89";
+    let expected = pattern.scan_all(data).into_iter().map(|offset| offset as u64).collect::<Vec<_>>();
+    assert_eq!(expected, vec![1, 6, 9]);
+    assert_eq!(offsets, expected);
+}
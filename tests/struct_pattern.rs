@@ -0,0 +1,40 @@
+#[test]
+/// Tests that the fields of a struct pattern can be located in the matches.
+fn struct_pattern_fields() {
+    let mut data = vec![0u8; 0x100];
+    data[0x40..0x48].copy_from_slice(&[0x48, 0x8B, 0x05, 0x78, 0x56, 0x34, 0x12, 0x90]);
+
+    let pattern = aobscan::StructPatternBuilder::new()
+        .field("opcode", &[0x48, 0x8B, 0x05])
+        .wildcard("disp", 4)
+        .field("suffix", &[0x90])
+        .build()
+        .unwrap();
+
+    assert_eq!(pattern.to_ida_style(), "48 8B 05 ? ? ? ? 90");
+    assert_eq!(pattern.field_offset("opcode"), Some(0));
+    assert_eq!(pattern.field_offset("disp"), Some(3));
+    assert_eq!(pattern.field_offset("suffix"), Some(7));
+    assert_eq!(pattern.field_offset("missing"), None);
+
+    let offsets = pattern.scan_all(&data);
+    assert_eq!(offsets, vec![0x40]);
+
+    let disp = offsets[0] + pattern.field_offset("disp").unwrap();
+    let value = u32::from_le_bytes(data[disp..disp + 4].try_into().unwrap());
+    assert_eq!(value, 0x12345678);
+}
+
+#[test]
+/// Tests that invalid struct patterns are rejected.
+fn struct_pattern_invalid() {
+    assert!(aobscan::StructPatternBuilder::new().build().is_err());
+
+    assert!(
+        aobscan::StructPatternBuilder::new()
+            .field("opcode", &[0x48])
+            .wildcard("opcode", 2)
+            .build()
+            .is_err()
+    );
+}
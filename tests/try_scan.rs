@@ -0,0 +1,83 @@
+use aobscan::ScanError;
+
+/// Builds a buffer with a match every 0x100 bytes.
+fn dense_data() -> Vec<u8> {
+    let mut data = vec![0u8; 0x10000];
+    for offset in (0..data.len()).step_by(0x100) {
+        data[offset..offset + 2].copy_from_slice(b"\x48\x8B");
+    }
+    data
+}
+
+#[test]
+/// Tests that a panic in the callback of a single-threaded scan is returned as an error.
+fn try_scan_callback_panicked() {
+    let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], 1);
+
+    let mut calls = 0;
+    let result = pattern.try_scan(&dense_data(), |_| {
+        calls += 1;
+        panic!("callback failure");
+    });
+
+    assert_eq!(result, Err(ScanError::CallbackPanicked));
+    assert_eq!(calls, 1);
+}
+
+#[test]
+/// Tests that a panic in the callback of a multi-threaded scan stops all the
+/// threads and is returned as an error, instead of hanging or propagating.
+fn try_scan_callback_panicked_multi_threaded() {
+    let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], 4);
+
+    let mut calls = 0;
+    let result = pattern.try_scan(&dense_data(), |offset| {
+        calls += 1;
+        if offset >= 0x8000 {
+            panic!("callback failure");
+        }
+        true
+    });
+
+    assert_eq!(result, Err(ScanError::CallbackPanicked));
+    assert!(calls < 0x100);
+}
+
+#[test]
+/// Tests that scans whose callback does not panic behave like normal scans.
+fn try_scan_success() {
+    let pattern = aobscan::Pattern::new(vec![0x48, 0x8B], vec![true, true], 4);
+
+    let mut count = 0;
+    assert_eq!(pattern.try_scan(&dense_data(), |_| {
+        count += 1;
+        true
+    }), Ok(true));
+    assert_eq!(count, 0x100);
+
+    assert_eq!(pattern.try_scan(&[0u8; 0x100], |_| true), Ok(false));
+}
+
+#[test]
+/// Tests that the pattern fits data that is at least as long as it.
+fn pattern_fits() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? 48 8B 05")
+        .unwrap()
+        .build();
+
+    assert!(!pattern.fits(3));
+    assert!(pattern.fits(4));
+    assert!(pattern.fits(5));
+}
+
+#[test]
+/// Tests that scans of data shorter than the pattern return an error.
+fn try_scan_data_too_small() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("? 48 8B 05")
+        .unwrap()
+        .build();
+
+    assert_eq!(pattern.try_scan(&[0x90, 0x48, 0x8B], |_| true), Err(ScanError::DataTooSmall));
+    assert_eq!(pattern.try_scan(&[0x90, 0x48, 0x8B, 0x05], |_| true), Ok(true));
+    assert_eq!(pattern.try_scan(&[0x90, 0x48, 0x8B, 0x06, 0x90], |_| true), Ok(false));
+}
@@ -0,0 +1,34 @@
+use common::*;
+
+mod common;
+
+#[test]
+/// Tests that the first sample containing the pattern is flagged by its index.
+fn validate_against_flags_sample() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("48 8B 05 ? ? ? ? 48 85 C0")
+        .unwrap()
+        .build();
+
+    let first = random_bytes_seeded(0x1000, 1);
+    let second = random_bytes_seeded(0x1000, 2);
+    let mut third = random_bytes_seeded(0x1000, 3);
+    third[0x800..0x80A].copy_from_slice(&[0x48, 0x8B, 0x05, 0x10, 0x20, 0x30, 0x40, 0x48, 0x85, 0xC0]);
+    let mut fourth = random_bytes_seeded(0x1000, 4);
+    fourth[0..10].copy_from_slice(&third[0x800..0x80A]);
+
+    let samples: Vec<&[u8]> = vec![&first, &second, &third, &fourth];
+    assert_eq!(pattern.validate_against(&samples), Err(2));
+    assert_eq!(pattern.validate_against(&samples[..2]), Ok(()));
+    assert_eq!(pattern.validate_against(&samples[3..]), Err(0));
+}
+
+#[test]
+/// Tests that an empty corpus and empty samples are always valid.
+fn validate_against_empty() {
+    let pattern = aobscan::PatternBuilder::from_ida_style("90")
+        .unwrap()
+        .build();
+
+    assert_eq!(pattern.validate_against(&[]), Ok(()));
+    assert_eq!(pattern.validate_against(&[&[], &[0x00]]), Ok(()));
+}
@@ -0,0 +1,49 @@
+use common::*;
+
+mod common;
+
+/// Finds the matches of a signature by comparing every byte at every position.
+fn naive_scan(data: &[u8], signature: &[Option<u8>]) -> Vec<usize> {
+    (0..=data.len().saturating_sub(signature.len()))
+        .filter(|&offset| offset + signature.len() <= data.len())
+        .filter(|&offset| {
+            signature.iter()
+                .zip(&data[offset..])
+                .all(|(byte, &data)| byte.is_none_or(|byte| byte == data))
+        })
+        .collect()
+}
+
+#[test]
+/// Tests that the patterns with long wildcard gaps find the same matches as a
+/// naive byte-by-byte comparison.
+fn wildcard_gaps_naive() {
+    let mut data = random_bytes_seeded(0x10000, 0x0B5CA4);
+
+    let gaps: &[&[usize]] = &[&[200], &[1], &[3, 500], &[0, 0, 64]];
+    for gap_lengths in gaps {
+        // Alternate a couple of fixed bytes with each gap.
+        let mut signature = vec![Some(0x48), Some(0x8B)];
+        for (index, &gap) in gap_lengths.iter().enumerate() {
+            signature.extend(std::iter::repeat_n(None, gap));
+            signature.extend([Some(0x90 + index as u8), Some(0xC3)]);
+        }
+
+        // Plant the signature at a few offsets, filling the gaps with random bytes.
+        for offset in [0x100, 0x4000, data.len() - signature.len()] {
+            for (index, byte) in signature.iter().enumerate() {
+                if let Some(byte) = byte {
+                    data[offset + index] = *byte;
+                }
+            }
+        }
+
+        let pattern = aobscan::PatternBuilder::from_iter_opt(signature.iter().copied())
+            .unwrap()
+            .build();
+
+        let expected = naive_scan(&data, &signature);
+        assert!(expected.len() >= 3);
+        assert_eq!(pattern.scan_all(&data), expected, "{:?}", gap_lengths);
+    }
+}